@@ -0,0 +1,265 @@
+// src-tauri/src/app_core.rs
+// 与界面无关的核心状态：配置目录、数据目录、登录信息、稍后提醒
+// 界面和命令行都通过 app.core() 使用同一份状态，命令只是外层的包装
+// 不依赖 AppHandle，可以在其他程序或测试中直接用 TaskReminderCore::new 创建
+// 任务保存后的统计、webhook 和钩子在核心中完成，托盘、窗口等界面相关的处理通过事件通知：
+//   task_saved        添加或修改任务成功，带任务
+//   task_completed    任务从未完成变为完成，带任务
+//   reminder_snoozed  稍后提醒，带分钟数
+// 提醒循环的状态（待响应的提醒、升级等级、下次提醒时间）也放在核心中，每个核心各自独立；
+// 托盘菜单、窗口位置、快速添加窗口等只在界面进程中存在的状态仍放在各自的模块中
+use crate::escalation::EscalationState;
+use crate::notion::AuthInfo;
+use crate::setting::{default_setting, Setting};
+use crate::setting_store::{self, SETTING_FILE};
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tauri::{App, AppHandle, Manager, Runtime};
+
+pub const AUTH_FILE: &str = "auth_info.json";
+
+type EventSink = Box<dyn Fn(&str, Value) + Send + Sync>;
+
+pub struct TaskReminderCore {
+    config_dir: PathBuf,                      // setting.json 等本机配置所在目录
+    data_dir: Mutex<Option<PathBuf>>,         // 当前的数据目录，首次使用时从配置中读取
+    auth_info: Mutex<Option<AuthInfo>>,       // Notion 登录信息
+    remind_later: Mutex<u64>,                 // 稍后提醒的间隔（分钟），0 表示使用配置的提醒频率
+    warning: Mutex<Option<String>>,           // 最近一次的配置警告，窗口打开后获取
+    pending_reminder: Mutex<Option<String>>,  // 尚未得到响应的提醒（fired_at），见 reminder_log
+    escalation: Mutex<EscalationState>,       // 升级提醒跟踪的任务和等级，见 escalation
+    next_reminder_at: Mutex<Option<Instant>>, // 下次提醒的时间点，未在计时时为 None
+    events: Option<EventSink>,                // 通知界面的事件，命令行中没有
+    notion_api_url: Option<String>,           // 为空时使用环境变量 VITE_NOTION_API_URL
+}
+
+impl TaskReminderCore {
+    pub fn new(config_dir: PathBuf) -> Self {
+        TaskReminderCore {
+            config_dir,
+            data_dir: Mutex::new(None),
+            auth_info: Mutex::new(None),
+            remind_later: Mutex::new(0),
+            warning: Mutex::new(None),
+            pending_reminder: Mutex::new(None),
+            escalation: Mutex::new(EscalationState::default()),
+            next_reminder_at: Mutex::new(None),
+            events: None,
+            notion_api_url: None,
+        }
+    }
+
+    /// 指定 Notion 接口地址，测试时使用本地的模拟服务，见 mock_notion
+    #[cfg(test)]
+    pub fn with_notion_api_url(mut self, url: impl Into<String>) -> Self {
+        self.notion_api_url = Some(url.into());
+        self
+    }
+
+    pub fn notion_api_url(&self) -> String {
+        match &self.notion_api_url {
+            Some(url) => url.clone(),
+            None => std::env::var("VITE_NOTION_API_URL").unwrap_or_default(),
+        }
+    }
+
+    /// 设置事件通知，例如转发给所有窗口
+    pub fn with_events(mut self, events: impl Fn(&str, Value) + Send + Sync + 'static) -> Self {
+        self.events = Some(Box::new(events));
+        self
+    }
+
+    pub fn emit(&self, event: &str, payload: impl Serialize) {
+        let Some(events) = &self.events else {
+            return;
+        };
+        match serde_json::to_value(payload) {
+            Ok(payload) => events(event, payload),
+            Err(e) => log::error!("emit {} error: {:?}", event, e),
+        }
+    }
+
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    // 配置
+
+    /// 加载配置，文件不存在时使用默认配置
+    pub fn load_setting(&self) -> Result<Setting, String> {
+        if !self.config_dir.join(SETTING_FILE).exists() {
+            return Ok(default_setting(&self.config_dir));
+        }
+        setting_store::read_setting(self)
+    }
+
+    /// 保存配置，返回写入的内容
+    pub fn save_setting(&self, setting: &Setting) -> Result<String, String> {
+        setting_store::write_setting(setting, &self.config_dir)
+    }
+
+    /// 记录配置警告并通知界面
+    pub fn warn(&self, message: String) {
+        log::warn!("{}", message);
+        *self.warning.lock().unwrap() = Some(message.clone());
+        self.emit("setting_warning", message);
+    }
+
+    pub fn take_warning(&self) -> Option<String> {
+        self.warning.lock().unwrap().take()
+    }
+
+    // 数据目录
+
+    /// 当前的数据目录，未配置时使用配置目录
+    pub fn data_dir(&self) -> Result<PathBuf, String> {
+        if let Some(dir) = self.data_dir.lock().unwrap().clone() {
+            return Ok(dir);
+        }
+        let path = self.load_setting()?.path;
+        let dir = self.resolve_data_dir(&path)?;
+        *self.data_dir.lock().unwrap() = Some(dir.clone());
+        Ok(dir)
+    }
+
+    /// 数据目录下的文件路径，目录不存在时创建
+    pub fn data_file(&self, name: &str) -> Result<PathBuf, String> {
+        let dir = self.data_dir()?;
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        Ok(dir.join(name))
+    }
+
+    /// 切换数据目录，不迁移文件，迁移见 data_dir::relocate
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+    }
+
+    /// Setting.path 对应的目录，为空时使用配置目录
+    pub fn resolve_data_dir(&self, path: &str) -> Result<PathBuf, String> {
+        if path.trim().is_empty() {
+            return Ok(self.config_dir.clone());
+        }
+        let dir = PathBuf::from(path.trim());
+        if !dir.is_absolute() {
+            return Err(format!("数据目录必须是绝对路径: {}", path));
+        }
+        Ok(dir)
+    }
+
+    // 登录信息
+
+    pub fn auth_info(&self) -> Option<AuthInfo> {
+        self.auth_info.lock().unwrap().clone()
+    }
+
+    /// 从文件重新读取登录信息
+    pub fn reload_auth_info(&self) -> Result<Option<AuthInfo>, String> {
+        let file_path = self.data_file(AUTH_FILE)?;
+        self.migrate_auth_info(&file_path);
+        let auth = if file_path.exists() {
+            let content =
+                fs::read_to_string(&file_path).map_err(|e| format!("读取文件失败: {}", e))?;
+            let auth: AuthInfo =
+                serde_json::from_str(&content).map_err(|e| format!("解析 JSON 失败: {}", e))?;
+            Some(auth)
+        } else {
+            None
+        };
+        *self.auth_info.lock().unwrap() = auth.clone();
+        Ok(auth)
+    }
+
+    // 以前的版本把登录信息保存在配置目录，数据目录不同时第一次读取前移到数据目录
+    fn migrate_auth_info(&self, file_path: &Path) {
+        let old_path = self.config_dir.join(AUTH_FILE);
+        if file_path.exists() || !old_path.exists() || old_path == file_path {
+            return;
+        }
+        match fs::copy(&old_path, file_path) {
+            Ok(_) => {
+                log::info!("auth info moved to {:?}", file_path);
+                if let Err(e) = fs::remove_file(&old_path) {
+                    log::error!("remove {:?} error: {:?}", old_path, e);
+                }
+            }
+            Err(e) => log::error!("move auth info to {:?} error: {:?}", file_path, e),
+        }
+    }
+
+    pub fn save_auth_info(&self, auth: &AuthInfo) -> Result<(), String> {
+        let file_path = self.data_file(AUTH_FILE)?;
+        let json = serde_json::to_string_pretty(auth).map_err(|e| e.to_string())?;
+        fs::write(&file_path, json).map_err(|e| e.to_string())?;
+        *self.auth_info.lock().unwrap() = Some(auth.clone());
+        Ok(())
+    }
+
+    /// 退出登录，清空内存和文件中的登录信息
+    pub fn clear_auth_info(&self) -> Result<(), String> {
+        *self.auth_info.lock().unwrap() = None;
+        let file_path = self.data_file(AUTH_FILE)?;
+        if file_path.exists() {
+            fs::remove_file(&file_path).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    // 稍后提醒
+
+    pub fn set_remind_later(&self, minutes: u64) {
+        *self.remind_later.lock().unwrap() = minutes;
+    }
+
+    /// 下一次提醒的间隔（分钟），未设置稍后提醒时使用配置的提醒频率
+    pub fn remind_later(&self) -> u64 {
+        let minutes = *self.remind_later.lock().unwrap();
+        if minutes > 0 {
+            return minutes;
+        }
+        self.load_setting()
+            .map(|setting| setting.remind_time)
+            .unwrap_or(60)
+    }
+
+    // 提醒循环
+
+    pub fn pending_reminder(&self) -> MutexGuard<'_, Option<String>> {
+        self.pending_reminder.lock().unwrap()
+    }
+
+    pub fn escalation(&self) -> MutexGuard<'_, EscalationState> {
+        self.escalation.lock().unwrap()
+    }
+
+    pub fn set_next_reminder_at(&self, next: Option<Instant>) {
+        *self.next_reminder_at.lock().unwrap() = next;
+    }
+
+    /// 距离下次提醒的剩余时间
+    pub fn next_reminder_in(&self) -> Option<Duration> {
+        let next = (*self.next_reminder_at.lock().unwrap())?;
+        Some(next.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// 从 AppHandle 或 App 获取核心状态，需要先在 setup 中 manage
+pub trait CoreExt {
+    fn core(&self) -> &TaskReminderCore;
+}
+
+impl<R: Runtime> CoreExt for AppHandle<R> {
+    fn core(&self) -> &TaskReminderCore {
+        self.state::<TaskReminderCore>().inner()
+    }
+}
+
+impl<R: Runtime> CoreExt for App<R> {
+    fn core(&self) -> &TaskReminderCore {
+        self.state::<TaskReminderCore>().inner()
+    }
+}
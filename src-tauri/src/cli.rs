@@ -0,0 +1,294 @@
+// src-tauri/src/cli.rs
+// 命令行：与界面共用登录信息、配置和任务逻辑，便于在终端和脚本中使用
+//   task-reminder add 明天下午3点 写周报 #工作
+//   task-reminder list --week --json
+//   task-reminder done 2
+//   task-reminder snooze 30m
+//   task-reminder tags 1 +work -home
+// 退出码：0 成功，1 执行失败，2 参数错误，3 未登录
+use crate::app_core::TaskReminderCore;
+use crate::hooks::wait_for_hooks;
+use crate::slash_command::{
+    execute, load_view, parse_slash_command, CommandResult, SlashCommand, TaskView,
+};
+use crate::task_manager::{add_task_impl, load_tags_impl, Task};
+use crate::task_parser::parse_task_text;
+use crate::webhook::wait_for_deliveries;
+
+use chrono::{DateTime, Local};
+use clap::{Args, Parser, Subcommand};
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2; // 与 clap 参数错误的退出码一致
+pub const EXIT_UNAUTHORIZED: i32 = 3;
+
+// 以这些参数开头时作为命令行运行，其他参数（例如开机启动时传入的参数）仍然启动界面
+const CLI_ARGS: &[&str] = &[
+    "add",
+    "list",
+    "done",
+    "snooze",
+    "tags",
+    "help",
+    "--help",
+    "-h",
+    "--version",
+    "-V",
+    "--json",
+];
+
+#[derive(Parser)]
+#[command(
+    name = "task-reminder",
+    version,
+    about = "高效习惯养成计划，专注每日最重要的事。"
+)]
+pub struct Cli {
+    /// 以 JSON 格式输出，便于脚本处理
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 添加任务，支持自然语言，例如 "明天下午3点 写周报 #工作 30%"
+    Add {
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// 查看任务，默认为今日未完成的任务
+    List {
+        #[command(flatten)]
+        view: ViewArgs,
+    },
+    /// 完成任务，序号与 list 的输出一致
+    Done { index: usize },
+    /// 稍后提醒，例如 30m、1h，不带单位时按小时
+    Snooze { duration: String },
+    /// 查看所有标签，或修改任务的标签，例如 tags 1 +work -home
+    Tags {
+        index: Option<usize>,
+
+        #[arg(allow_hyphen_values = true, trailing_var_arg = true)]
+        changes: Vec<String>,
+    },
+}
+
+#[derive(Args)]
+#[group(multiple = false)]
+struct ViewArgs {
+    /// 今日未完成
+    #[arg(long)]
+    today: bool,
+    /// 本周未完成
+    #[arg(long)]
+    week: bool,
+    /// 今天之前未完成
+    #[arg(long)]
+    overdue: bool,
+    /// 今日已完成
+    #[arg(long)]
+    done: bool,
+}
+
+impl ViewArgs {
+    fn view(&self) -> TaskView {
+        if self.week {
+            TaskView::Week
+        } else if self.overdue {
+            TaskView::Overdue
+        } else if self.done {
+            TaskView::Done
+        } else {
+            TaskView::Today
+        }
+    }
+}
+
+/// 启动参数是命令行子命令时解析参数，否则返回 None 启动界面
+pub fn parse_args() -> Option<Cli> {
+    let first = std::env::args().nth(1)?;
+    if !CLI_ARGS.contains(&first.as_str()) {
+        return None;
+    }
+    attach_console();
+    Some(Cli::parse())
+}
+
+/// 不创建 Tauri 应用（没有图形界面的环境也能运行），执行命令后返回退出码
+pub fn run(cli: Cli, context: tauri::Context<tauri::Wry>) -> i32 {
+    let resource_dir =
+        tauri::utils::platform::resource_dir(context.package_info(), &tauri::Env::default());
+    crate::load_env(resource_dir.ok());
+    // 与界面的 app_config_dir 相同
+    let Some(config_dir) = dirs::config_dir() else {
+        eprintln!("启动失败: 无法获取配置目录");
+        return EXIT_FAILED;
+    };
+    let core = TaskReminderCore::new(config_dir.join(&context.config().identifier));
+    if let Err(e) = core.reload_auth_info() {
+        log::error!("load auth info error: {}", e);
+    }
+
+    let json = cli.json;
+    let (code, result) = tauri::async_runtime::block_on(run_command(cli.command, &core));
+    print_result(&result, json);
+    // 添加、完成任务时触发的 webhook 和事件钩子在后台执行，退出前等待，webhook 失败时会等到重试结束
+    wait_for_deliveries();
+    wait_for_hooks();
+    code
+}
+
+async fn run_command(command: Command, core: &TaskReminderCore) -> (i32, CommandResult) {
+    // 稍后提醒只修改本机的提醒计时，其他命令需要读写 Notion 中的任务
+    if !matches!(command, Command::Snooze { .. }) && core.auth_info().is_none() {
+        return (
+            EXIT_UNAUTHORIZED,
+            CommandResult::failed("auth", "未登录 Notion，请先在应用中登录".to_string()),
+        );
+    }
+
+    let result = match command {
+        Command::Add { text } => add(&text.join(" "), core).await,
+        Command::List { view } => {
+            let tasks = load_view(view.view(), core).await;
+            tasks.map(|tasks| {
+                let mut result = CommandResult::ok("list", format!("共 {} 个任务", tasks.len()));
+                result.tasks = Some(tasks);
+                result
+            })
+        }
+        Command::Done { index } => execute(SlashCommand::Done { index }, core).await,
+        Command::Snooze { duration } => Ok(snooze(&duration)),
+        Command::Tags { index: None, .. } => tags(core).await,
+        Command::Tags {
+            index: Some(index),
+            changes,
+        } => match parse_slash_command(&format!("/tag {} {}", index, changes.join(" "))) {
+            Ok(command) => execute(command, core).await,
+            Err(e) => return (EXIT_USAGE, CommandResult::failed("tags", e)),
+        },
+    };
+
+    match result {
+        Ok(result) if result.success => (EXIT_OK, result),
+        Ok(result) => (EXIT_FAILED, result),
+        Err(e) => (EXIT_FAILED, CommandResult::failed("error", e)),
+    }
+}
+
+async fn add(text: &str, core: &TaskReminderCore) -> Result<CommandResult, String> {
+    let mut task = parse_task_text(text, Local::now()).task;
+    if task.text.is_empty() {
+        return Ok(CommandResult::failed("add", "任务内容不能为空".to_string()));
+    }
+    let result = add_task_impl(&task, core).await?;
+    if !result.success {
+        return Ok(CommandResult::failed(
+            "add",
+            result.error.unwrap_or_else(|| "创建失败".to_string()),
+        ));
+    }
+    task.id = result.id.unwrap_or_default();
+
+    let mut result = CommandResult::ok("add", format!("已添加: {}", task.text));
+    result.task = Some(task);
+    Ok(result)
+}
+
+// 命令行是单独的进程，通过本地脚本接口通知运行中的应用
+fn snooze(duration: &str) -> CommandResult {
+    let minutes = match parse_slash_command(&format!("/snooze {}", duration)) {
+        Ok(SlashCommand::Snooze { minutes }) => minutes,
+        Ok(_) => return CommandResult::failed("snooze", format!("无法识别的时长: {}", duration)),
+        Err(e) => return CommandResult::failed("snooze", e),
+    };
+
+    #[cfg(unix)]
+    let result = crate::ipc::request("snooze", serde_json::json!({ "minutes": minutes }));
+    // Windows 上没有本地脚本接口
+    #[cfg(not(unix))]
+    let result: Result<serde_json::Value, String> =
+        Err("当前系统不支持在命令行中稍后提醒，请在应用中操作".to_string());
+
+    match result {
+        Ok(_) => CommandResult::ok("snooze", format!("{} 分钟后再提醒", minutes)),
+        Err(e) => CommandResult::failed("snooze", e),
+    }
+}
+
+async fn tags(core: &TaskReminderCore) -> Result<CommandResult, String> {
+    let result = load_tags_impl(core).await?;
+    if !result.success {
+        return Ok(CommandResult::failed(
+            "tags",
+            result.error.unwrap_or_else(|| "加载标签失败".to_string()),
+        ));
+    }
+    let tags = result.tags.unwrap_or_default();
+    let mut result = CommandResult::ok("tags", format!("共 {} 个标签", tags.len()));
+    result.tags = Some(tags);
+    Ok(result)
+}
+
+fn print_result(result: &CommandResult, json: bool) {
+    if json {
+        match serde_json::to_string_pretty(result) {
+            Ok(text) => println!("{}", text),
+            Err(e) => eprintln!("{}", e),
+        }
+        return;
+    }
+    if !result.success {
+        eprintln!("{}", result.message);
+        return;
+    }
+
+    if let Some(tasks) = &result.tasks {
+        for (i, task) in tasks.iter().enumerate() {
+            println!("{}", format_task(i + 1, task));
+        }
+    }
+    if let Some(tags) = &result.tags {
+        for tag in tags {
+            println!("{}", tag);
+        }
+    }
+    println!("{}", result.message);
+}
+
+// 例如 "2. [ ] 10-21 15:00  写周报 #工作 30%"
+fn format_task(index: usize, task: &Task) -> String {
+    let check = if task.is_done() { "x" } else { " " };
+    let time = DateTime::parse_from_rfc3339(&task.time.start)
+        .map(|time| time.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| task.time.start.clone());
+
+    let mut line = format!("{}. [{}] {}  {}", index, check, time, task.text);
+    for tag in task.tags.iter().flatten() {
+        line.push_str(&format!(" #{}", tag));
+    }
+    if task.percent > 0 {
+        line.push_str(&format!(" {}%", task.percent));
+    }
+    line
+}
+
+// release 版本在 Windows 上没有控制台，输出到启动它的终端
+#[cfg(windows)]
+fn attach_console() {
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
@@ -0,0 +1,203 @@
+// src-tauri/src/data_dir.rs
+// 数据目录的迁移：任务、授权信息、提醒记录等数据文件保存在 Setting.path 指定的目录
+// setting.json 和窗口位置等本机状态仍在配置目录，不随数据目录移动
+// 当前的数据目录见 TaskReminderCore::data_dir
+use crate::app_core::TaskReminderCore;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 需要随数据目录迁移的文件
+pub const DATA_FILES: &[&str] = &[
+    "tasks.json",
+    "auth_info.json",
+    "reminder_log.json",
+    "webhook_log.json",
+];
+
+/// 一次数据目录迁移：文件先复制到新目录，配置保存成功后 commit 删除旧文件，失败时 rollback
+pub struct Relocation {
+    from: PathBuf,
+    to: PathBuf,
+    created: Vec<PathBuf>, // 新目录中复制出的文件
+}
+
+/// 数据目录改变时把数据文件复制到新目录，目录相同时返回 None
+/// 新目录中已有数据文件时拒绝迁移，不覆盖其中的数据
+pub fn relocate(path: &str, core: &TaskReminderCore) -> Result<Option<Relocation>, String> {
+    let from = core.data_dir()?;
+    let to = core.resolve_data_dir(path)?;
+    if same_dir(&from, &to) {
+        return Ok(None);
+    }
+    log::info!("relocate data dir: {:?} -> {:?}", from, to);
+
+    fs::create_dir_all(&to).map_err(|e| format!("无法创建数据目录 {:?}: {}", to, e))?;
+    check_writable(&to)?;
+    let existing: Vec<&str> = DATA_FILES
+        .iter()
+        .copied()
+        .filter(|name| to.join(name).exists())
+        .collect();
+    if !existing.is_empty() {
+        return Err(format!(
+            "新目录中已有数据文件 {}，请先移走或选择其他目录",
+            existing.join(", ")
+        ));
+    }
+
+    let mut relocation = Relocation {
+        from,
+        to,
+        created: vec![],
+    };
+    if let Err(e) = relocation.copy_files() {
+        relocation.rollback();
+        return Err(e);
+    }
+    Ok(Some(relocation))
+}
+
+impl Relocation {
+    fn copy_files(&mut self) -> Result<(), String> {
+        for name in DATA_FILES {
+            let source = self.from.join(name);
+            if !source.exists() {
+                continue;
+            }
+            let target = self.to.join(name);
+            // 先复制为临时文件再重命名，避免留下不完整的文件
+            let tmp = self.to.join(format!("{}.tmp", name));
+            if let Err(e) = fs::copy(&source, &tmp) {
+                let _ = fs::remove_file(&tmp);
+                return Err(format!("复制 {} 失败: {}", name, e));
+            }
+            fs::rename(&tmp, &target).map_err(|e| e.to_string())?;
+            self.created.push(target);
+        }
+        Ok(())
+    }
+
+    /// 配置已保存，切换到新目录并删除旧文件
+    pub fn commit(self, core: &TaskReminderCore) {
+        core.set_data_dir(self.to.clone());
+        for name in DATA_FILES {
+            let source = self.from.join(name);
+            if source.exists() {
+                if let Err(e) = fs::remove_file(&source) {
+                    log::error!("remove {:?} error: {:?}", source, e);
+                }
+            }
+        }
+        log::info!("data dir moved to {:?}", self.to);
+    }
+
+    /// 迁移失败，删除复制的文件
+    pub fn rollback(self) {
+        for file in &self.created {
+            let _ = fs::remove_file(file);
+        }
+        log::info!("data dir relocation rolled back: {:?}", self.from);
+    }
+}
+
+// 写入测试文件检查目录是否可写
+fn check_writable(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(".task-reminder-write-test");
+    fs::write(&probe, b"ok").map_err(|e| format!("数据目录不可写 {:?}: {}", dir, e))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_core::AUTH_FILE;
+    use crate::mock_notion::test_auth;
+    use crate::setting::default_setting;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "task-reminder-data-dir-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn relocate_refuses_target_with_data_files() {
+        let config_dir = temp_dir("refuse-config");
+        let target = temp_dir("refuse-target");
+        fs::write(config_dir.join("tasks.json"), "old").unwrap();
+        fs::write(target.join("tasks.json"), "other").unwrap();
+        let core = TaskReminderCore::new(config_dir.clone());
+
+        match relocate(target.to_str().unwrap(), &core) {
+            Err(e) => assert!(e.contains("tasks.json"), "{}", e),
+            Ok(_) => panic!("relocate should refuse"),
+        }
+        assert_eq!(
+            fs::read_to_string(target.join("tasks.json")).unwrap(),
+            "other"
+        );
+        assert_eq!(
+            fs::read_to_string(config_dir.join("tasks.json")).unwrap(),
+            "old"
+        );
+
+        let _ = fs::remove_dir_all(config_dir);
+        let _ = fs::remove_dir_all(target);
+    }
+
+    #[test]
+    fn relocate_moves_files_on_commit() {
+        let config_dir = temp_dir("move-config");
+        let target = temp_dir("move-target");
+        fs::write(config_dir.join("tasks.json"), "old").unwrap();
+        let core = TaskReminderCore::new(config_dir.clone());
+
+        let relocation = relocate(target.to_str().unwrap(), &core).unwrap().unwrap();
+        relocation.commit(&core);
+        assert_eq!(core.data_dir().unwrap(), target);
+        assert_eq!(
+            fs::read_to_string(target.join("tasks.json")).unwrap(),
+            "old"
+        );
+        assert!(!config_dir.join("tasks.json").exists());
+
+        let _ = fs::remove_dir_all(config_dir);
+        let _ = fs::remove_dir_all(target);
+    }
+
+    #[test]
+    fn auth_info_moves_from_config_dir_once() {
+        let config_dir = temp_dir("auth-config");
+        let data_dir = temp_dir("auth-data");
+        let mut setting = default_setting(&config_dir);
+        setting.path = data_dir.to_string_lossy().to_string();
+        let core = TaskReminderCore::new(config_dir.clone());
+        core.save_setting(&setting).unwrap();
+
+        // 以前的版本写在配置目录
+        core.set_data_dir(config_dir.clone());
+        core.save_auth_info(&test_auth()).unwrap();
+        let core = TaskReminderCore::new(config_dir.clone());
+
+        assert!(core.reload_auth_info().unwrap().is_some());
+        assert!(data_dir.join(AUTH_FILE).exists());
+        assert!(!config_dir.join(AUTH_FILE).exists());
+
+        let _ = fs::remove_dir_all(config_dir);
+        let _ = fs::remove_dir_all(data_dir);
+    }
+}
@@ -0,0 +1,267 @@
+// src-tauri/src/deep_link.rs
+// 链接：可以放在文档、邮件和脚本中，点击后由应用处理
+//   task-reminder://add?text=写周报&tags=工作,周报&date=明天下午3点
+//   task-reminder://open/<task-id>
+// 链接可能来自任何网页，参数需要校验；添加链接只填入快速添加窗口，由用户回车确认后保存
+use crate::app_core::CoreExt;
+use crate::instance::{notify, show_main_window};
+use crate::shortcut::prefill_quick_add_window;
+use crate::task_manager::{is_task_id, load_task_from_notion_impl, Task};
+use crate::task_parser::build_task;
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+pub const SCHEME: &str = "task-reminder";
+
+const MAX_TEXT_LEN: usize = 500;
+const MAX_TAGS: usize = 10;
+
+// 待打开的任务，启动时主窗口还未监听事件，由前端主动获取
+static PENDING_OPEN: Lazy<Mutex<Option<Task>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, PartialEq)]
+pub enum DeepLink {
+    Add {
+        text: String,
+        tags: Vec<String>,
+        date: Option<String>,
+    },
+    Open {
+        id: String,
+    },
+}
+
+/// 处理启动时的链接并监听之后打开的链接
+pub fn init(app: &AppHandle) {
+    // 开发环境和未安装的程序（例如 AppImage）需要手动注册
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        log::error!("register deep link error: {:?}", e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle_urls(&handle, event.urls());
+    });
+
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => handle_urls(app, urls),
+        Ok(None) => {}
+        Err(e) => log::error!("get deep link error: {:?}", e),
+    }
+}
+
+pub fn handle_urls(app: &AppHandle, urls: Vec<Url>) {
+    for url in urls {
+        log::info!("open deep link: {}", url);
+        match parse_deep_link(&url).and_then(|link| handle_link(app, link)) {
+            Ok(()) => {}
+            Err(e) => {
+                log::warn!("deep link {} error: {}", url, e);
+                notify(app, format!("无法处理链接: {}", e));
+            }
+        }
+    }
+}
+
+/// 解析并校验链接
+pub fn parse_deep_link(url: &Url) -> Result<DeepLink, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("不支持的链接: {}", url.scheme()));
+    }
+
+    match url.host_str().unwrap_or_default().to_lowercase().as_str() {
+        "add" => {
+            let mut text = String::new();
+            let mut tags: Vec<String> = vec![];
+            let mut date = None;
+            for (key, value) in url.query_pairs() {
+                match key.as_ref() {
+                    "text" => text = value.trim().to_string(),
+                    "tags" => tags.extend(
+                        value
+                            .split(',')
+                            .map(|tag| tag.trim().trim_start_matches('#'))
+                            .filter(|tag| !tag.is_empty())
+                            .map(str::to_string),
+                    ),
+                    "date" => date = Some(value.trim().to_string()).filter(|d| !d.is_empty()),
+                    other => log::info!("ignore deep link param: {}", other),
+                }
+            }
+
+            if text.is_empty() {
+                return Err("缺少任务内容 text".to_string());
+            }
+            if text.chars().count() > MAX_TEXT_LEN {
+                return Err(format!("任务内容不能超过 {} 个字", MAX_TEXT_LEN));
+            }
+            if tags.len() > MAX_TAGS {
+                return Err(format!("标签不能超过 {} 个", MAX_TAGS));
+            }
+            if let Some(tag) = tags.iter().find(|tag| tag.chars().any(char::is_whitespace)) {
+                return Err(format!("标签不能包含空格: {}", tag));
+            }
+            Ok(DeepLink::Add { text, tags, date })
+        }
+        "open" => {
+            let id = url.path().trim_matches('/');
+            if !is_task_id(id) {
+                return Err(format!("无效的任务 id: {}", id));
+            }
+            Ok(DeepLink::Open { id: id.to_string() })
+        }
+        other => Err(format!("未知的链接操作: {}", other)),
+    }
+}
+
+fn handle_link(app: &AppHandle, link: DeepLink) -> Result<(), String> {
+    match link {
+        DeepLink::Add { text, tags, date } => {
+            // 先校验内容和日期，无法识别时直接提示
+            build_task(&text, tags.clone(), date.as_deref(), Local::now())?;
+            prefill_quick_add_window(app, quick_add_text(&text, &tags, date.as_deref()));
+        }
+        DeepLink::Open { id } => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                open_task(&app, &id).await;
+            });
+        }
+    }
+    Ok(())
+}
+
+// 快速添加窗口中的文本，例如 "明天下午3点 写周报 #工作 #周报"
+fn quick_add_text(text: &str, tags: &[String], date: Option<&str>) -> String {
+    let mut words: Vec<String> = date.map(str::to_string).into_iter().collect();
+    words.push(text.to_string());
+    words.extend(tags.iter().map(|tag| format!("#{}", tag)));
+    words.join(" ")
+}
+
+async fn open_task(app: &AppHandle, id: &str) {
+    match load_task_from_notion_impl(id, app.core()).await {
+        Ok(Some(task)) => {
+            *PENDING_OPEN.lock().unwrap() = Some(task);
+            show_main_window(app);
+            let _ = app.emit("open_task", ());
+        }
+        Ok(None) => notify(app, "任务不存在或已删除".to_string()),
+        Err(e) => notify(app, format!("打开任务失败: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub fn take_open_task() -> Result<Option<Task>, String> {
+    Ok(PENDING_OPEN.lock().unwrap().take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Result<DeepLink, String> {
+        parse_deep_link(&Url::parse(url).unwrap())
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_add_and_open_links() {
+        assert_eq!(
+            parse("task-reminder://add?text=%E5%86%99%E5%91%A8%E6%8A%A5&tags=%23work,%20weekly,&date=tomorrow%203pm"),
+            Ok(DeepLink::Add {
+                text: "写周报".to_string(),
+                tags: tags(&["work", "weekly"]),
+                date: Some("tomorrow 3pm".to_string()),
+            })
+        );
+        assert_eq!(
+            parse("task-reminder://ADD?text=review&date=%20&from=mail"),
+            Ok(DeepLink::Add {
+                text: "review".to_string(),
+                tags: vec![],
+                date: None,
+            })
+        );
+        assert_eq!(
+            parse("task-reminder://open/1a2b3c4d-5e6f-7a8b-9c0d-1e2f3a4b5c6d"),
+            Ok(DeepLink::Open {
+                id: "1a2b3c4d-5e6f-7a8b-9c0d-1e2f3a4b5c6d".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_links() {
+        let long_text = "字".repeat(MAX_TEXT_LEN + 1);
+        let many_tags = (0..=MAX_TAGS)
+            .map(|i| format!("t{}", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let cases = [
+            ("https://add?text=review".to_string(), "不支持的链接: https"),
+            (
+                "task-reminder://delete/1".to_string(),
+                "未知的链接操作: delete",
+            ),
+            (
+                "task-reminder://add?tags=work".to_string(),
+                "缺少任务内容 text",
+            ),
+            (
+                "task-reminder://add?text=%20%20".to_string(),
+                "缺少任务内容 text",
+            ),
+            (
+                format!("task-reminder://add?text={}", long_text),
+                "任务内容不能超过 500 个字",
+            ),
+            (
+                format!("task-reminder://add?text=review&tags={}", many_tags),
+                "标签不能超过 10 个",
+            ),
+            (
+                "task-reminder://add?text=review&tags=deep%20work".to_string(),
+                "标签不能包含空格: deep work",
+            ),
+            ("task-reminder://open/".to_string(), "无效的任务 id: "),
+            (
+                "task-reminder://open/1a2b3c4d".to_string(),
+                "无效的任务 id: 1a2b3c4d",
+            ),
+            (
+                "task-reminder://open/1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d/edit".to_string(),
+                "无效的任务 id: 1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d/edit",
+            ),
+        ];
+        for (url, error) in cases {
+            assert_eq!(parse(&url), Err(error.to_string()), "{}", url);
+        }
+
+        // 正好 500 个字、10 个标签时可以添加
+        let text = "字".repeat(MAX_TEXT_LEN);
+        let ten_tags = (0..MAX_TAGS)
+            .map(|i| format!("t{}", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let url = format!("task-reminder://add?text={}&tags={}", text, ten_tags);
+        assert!(parse(&url).is_ok());
+    }
+
+    #[test]
+    fn quick_add_text_puts_date_first_and_tags_last() {
+        assert_eq!(
+            quick_add_text("写周报", &tags(&["工作", "周报"]), Some("明天下午3点")),
+            "明天下午3点 写周报 #工作 #周报"
+        );
+        assert_eq!(quick_add_text("写周报", &[], None), "写周报");
+    }
+}
@@ -0,0 +1,229 @@
+// src-tauri/src/escalation.rs
+// 升级提醒：最重要的任务一直未开始时，逐步缩短提醒间隔、改为弹窗并置顶
+use crate::app_core::TaskReminderCore;
+use crate::task_manager::Task;
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// 弹窗出现后这么快就被关闭，视为没有看就忽略了
+const IGNORED_WITHIN: Duration = Duration::from_secs(10);
+// 弹窗一直没人理会，与 complete_within 的默认值相同
+const POPUP_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EscalationSetting {
+    pub enabled: bool,
+    pub shrink_percent: u32, // 每次被忽略后提醒间隔缩短的百分比
+    pub min_interval: u64,   // 最短提醒间隔（分钟）
+    pub notify_first: bool,  // 先用系统通知代替弹窗
+    pub popup_after: u32,    // 忽略多少次后改为弹窗
+    pub always_on_top: bool, // 继续忽略时将窗口置顶
+    pub on_top_after: u32,   // 忽略多少次后置顶
+}
+
+impl Default for EscalationSetting {
+    fn default() -> Self {
+        EscalationSetting {
+            enabled: false,
+            shrink_percent: 30,
+            min_interval: 5,
+            notify_first: true,
+            popup_after: 1,
+            always_on_top: false,
+            on_top_after: 3,
+        }
+    }
+}
+
+impl EscalationSetting {
+    // 按升级等级缩短提醒间隔，不短于 min_interval，也不长于原间隔
+    pub fn interval_minutes(&self, base: u64, level: u32) -> u64 {
+        if !self.enabled || level == 0 {
+            return base;
+        }
+        let keep = 1.0 - self.shrink_percent.min(100) as f64 / 100.0;
+        let minutes = (base as f64 * keep.powi(level as i32)).round() as u64;
+        minutes.max(self.min_interval).min(base)
+    }
+
+    // 当前等级是否仍只发系统通知
+    pub fn use_notification(&self, level: u32) -> bool {
+        self.enabled && self.notify_first && level < self.popup_after
+    }
+
+    pub fn keep_on_top(&self, level: u32) -> bool {
+        self.enabled && self.always_on_top && level >= self.on_top_after
+    }
+}
+
+// 保存在 TaskReminderCore 中
+#[derive(Default)]
+pub struct EscalationState {
+    task_id: Option<String>, // 正在跟踪的最重要任务
+    status: String,
+    level: u32,           // 连续被忽略的次数
+    fired: Option<Fired>, // 上次观察后发出的提醒
+}
+
+// 一次提醒的弹窗显示和关闭时间
+struct Fired {
+    shown_at: Option<Instant>,  // 只发出系统通知等、没有弹窗时为 None
+    hidden_at: Option<Instant>, // 弹窗被关闭的时间
+}
+
+impl Fired {
+    // 弹窗刚出现就被关闭，或一直没人理会直到超时，视为被忽略
+    // 没有弹窗的提醒无法关闭，没有开始任务或稍后提醒就算超时
+    fn ignored(&self) -> bool {
+        let Some(shown_at) = self.shown_at else {
+            return true;
+        };
+        match self.hidden_at {
+            Some(hidden_at) => {
+                let shown_for = hidden_at.saturating_duration_since(shown_at);
+                shown_for <= IGNORED_WITHIN || shown_for >= POPUP_TIMEOUT
+            }
+            None => false,
+        }
+    }
+}
+
+impl EscalationState {
+    fn observe(&mut self, top: Option<&Task>) -> u32 {
+        let unchanged = match top {
+            Some(task) => {
+                task.is_not_started()
+                    && self.task_id.as_deref() == Some(task.id.as_str())
+                    && self.status == task.status
+            }
+            None => false,
+        };
+
+        if unchanged {
+            if self.fired.as_ref().is_some_and(Fired::ignored) {
+                self.level += 1;
+                log::info!("escalation level: {}", self.level);
+            }
+        } else {
+            self.task_id = top.map(|task| task.id.clone());
+            self.status = top.map(|task| task.status.clone()).unwrap_or_default();
+            self.level = 0;
+        }
+        self.fired = None;
+        self.level
+    }
+
+    fn fired(&mut self, popup: bool, now: Instant) {
+        self.fired = Some(Fired {
+            shown_at: popup.then_some(now),
+            hidden_at: None,
+        });
+    }
+
+    // 只记录提醒弹窗第一次被关闭的时间
+    fn hidden(&mut self, now: Instant) {
+        if let Some(fired) = &mut self.fired {
+            if fired.shown_at.is_some() && fired.hidden_at.is_none() {
+                fired.hidden_at = Some(now);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.level = 0;
+        self.fired = None;
+    }
+}
+
+// 每轮计时开始前调用，返回当前升级等级
+// 最重要的任务变化或状态改变时重置，否则上一轮提醒被忽略则升级
+pub fn observe(top: Option<&Task>, core: &TaskReminderCore) -> u32 {
+    core.escalation().observe(top)
+}
+
+// 发出提醒后调用，popup 表示是否弹出了窗口
+pub fn mark_fired(popup: bool, core: &TaskReminderCore) {
+    core.escalation().fired(popup, Instant::now());
+}
+
+// 弹窗被关闭时调用
+pub fn mark_hidden(core: &TaskReminderCore) {
+    core.escalation().hidden(Instant::now());
+}
+
+// 用户主动稍后提醒时重置
+pub fn reset(core: &TaskReminderCore) {
+    core.escalation().reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_notion::test_task;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    fn task(id: &str, status: &str) -> Task {
+        let mut task = test_task("写周报", status, "2026-10-19");
+        task.id = id.to_string();
+        task
+    }
+
+    // 弹出提醒，显示 shown_for 后关闭
+    fn popup(state: &mut EscalationState, shown_for: Duration) {
+        let now = Instant::now();
+        state.fired(true, now);
+        state.hidden(now + shown_for);
+    }
+
+    #[test]
+    fn escalates_only_ignored_reminders() {
+        let top = task("a", "未开始");
+        let mut state = EscalationState::default();
+        assert_eq!(state.observe(Some(&top)), 0);
+
+        // 没有提醒
+        assert_eq!(state.observe(Some(&top)), 0);
+        // 弹窗刚出现就被关闭
+        popup(&mut state, 3 * SECOND);
+        assert_eq!(state.observe(Some(&top)), 1);
+        // 看了一会儿再关闭
+        popup(&mut state, 60 * SECOND);
+        assert_eq!(state.observe(Some(&top)), 1);
+        // 一直没人理会
+        popup(&mut state, POPUP_TIMEOUT);
+        assert_eq!(state.observe(Some(&top)), 2);
+        // 只发了系统通知
+        state.fired(false, Instant::now());
+        assert_eq!(state.observe(Some(&top)), 3);
+        // 弹窗还没关闭
+        state.fired(true, Instant::now());
+        assert_eq!(state.observe(Some(&top)), 3);
+    }
+
+    #[test]
+    fn resets_when_top_task_changes() {
+        let top = task("a", "未开始");
+        let ignore = |state: &mut EscalationState, top: &Task| {
+            state.observe(Some(top));
+            popup(state, SECOND);
+            assert_eq!(state.observe(Some(top)), 1);
+            popup(state, SECOND);
+        };
+
+        let mut state = EscalationState::default();
+        ignore(&mut state, &top);
+        assert_eq!(state.observe(Some(&task("b", "未开始"))), 0);
+
+        ignore(&mut state, &top);
+        assert_eq!(state.observe(Some(&task("a", "进行中"))), 0);
+
+        ignore(&mut state, &top);
+        assert_eq!(state.observe(None), 0);
+
+        ignore(&mut state, &top);
+        state.reset();
+        assert_eq!(state.observe(Some(&top)), 0);
+    }
+}
@@ -0,0 +1,333 @@
+// src-tauri/src/export.rs
+// 导出任务：按日期范围、状态和标签查询，保存为 CSV、JSON 或 Markdown 清单，便于写周报
+// JSON 为 Task 数组，可以再由 import_tasks 导入
+use crate::app_core::{CoreExt, TaskReminderCore};
+use crate::task_manager::{load_tasks_impl, Task, TaskParams};
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const CSV_HEADERS: [&str; 7] = ["id", "text", "status", "percent", "start", "end", "tags"];
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct ExportQuery {
+    pub start: Option<String>, // 开始日期，YYYY-MM-DD 或 RFC 3339，为空时不限制
+    pub end: Option<String>,   // 结束日期，包含当天
+    pub status: Option<String>, // 与 TaskParams 相同，"0" 所有，"1" 未完成，"2" 完成
+    #[serde(default)]
+    pub tags: Vec<String>, // 包含其中任一标签，为空时不限制
+}
+
+#[derive(Serialize)]
+pub struct ExportResult {
+    pub count: usize,
+    pub path: String, // 实际写入的文件
+}
+
+#[tauri::command]
+pub async fn export_tasks(
+    query: ExportQuery,
+    format: ExportFormat,
+    path: String,
+    app: tauri::AppHandle,
+) -> Result<ExportResult, String> {
+    let tasks = query_tasks(&query, app.core()).await?;
+    let content = export_content(&tasks, format, &query)?;
+
+    let path = output_path(&path, format)?;
+    std::fs::write(&path, content).map_err(|e| format!("写入 {:?} 失败: {}", path, e))?;
+    log::info!("exported {} tasks to {:?}", tasks.len(), path);
+    Ok(ExportResult {
+        count: tasks.len(),
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+/// 按格式生成导出文件的内容
+pub fn export_content(
+    tasks: &[Task],
+    format: ExportFormat,
+    query: &ExportQuery,
+) -> Result<String, String> {
+    match format {
+        ExportFormat::Csv => to_csv(tasks),
+        ExportFormat::Json => serde_json::to_string_pretty(tasks).map_err(|e| e.to_string()),
+        ExportFormat::Markdown => Ok(to_markdown(tasks, query)),
+    }
+}
+
+pub async fn query_tasks(
+    query: &ExportQuery,
+    core: &TaskReminderCore,
+) -> Result<Vec<Task>, String> {
+    let params = TaskParams {
+        start: date_bound(query.start.as_deref(), NaiveTime::MIN)?,
+        end: date_bound(
+            query.end.as_deref(),
+            NaiveTime::from_hms_opt(23, 59, 59).unwrap(),
+        )?,
+        status: Some(query.status.clone().unwrap_or_else(|| "0".to_string())),
+    };
+    let result = load_tasks_impl(&Some(params), core).await?;
+    if !result.success {
+        return Err(result
+            .error
+            .or(result.status)
+            .unwrap_or_else(|| "加载任务失败".to_string()));
+    }
+
+    let mut tasks = result.tasks.map(|list| list.tasks).unwrap_or_default();
+    if !query.tags.is_empty() {
+        tasks.retain(|task| {
+            task.tags
+                .iter()
+                .flatten()
+                .any(|tag| query.tags.contains(tag))
+        });
+    }
+    tasks.sort_by(|a, b| a.time.start.cmp(&b.time.start));
+    Ok(tasks)
+}
+
+// 只有日期时取当天的开始或结束时间
+fn date_bound(date: Option<&str>, time: NaiveTime) -> Result<Option<String>, String> {
+    let Some(date) = date.map(str::trim).filter(|d| !d.is_empty()) else {
+        return Ok(None);
+    };
+    if DateTime::parse_from_rfc3339(date).is_ok() {
+        return Ok(Some(date.to_string()));
+    }
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("无法识别的日期: {}", date))?;
+    Local
+        .from_local_datetime(&day.and_time(time))
+        .earliest()
+        .map(|time| Some(time.to_rfc3339()))
+        .ok_or_else(|| format!("无法识别的日期: {}", date))
+}
+
+// 路径是目录时使用默认文件名，例如 tasks-2026-10-19.md
+fn output_path(path: &str, format: ExportFormat) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("请选择导出的位置".to_string());
+    }
+    let mut path = PathBuf::from(path);
+    if path.is_dir() {
+        let name = format!(
+            "tasks-{}.{}",
+            Local::now().format("%Y-%m-%d"),
+            format.extension()
+        );
+        path.push(name);
+    }
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => {
+            Err(format!("目录不存在: {:?}", parent))
+        }
+        _ => Ok(path),
+    }
+}
+
+fn to_csv(tasks: &[Task]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer
+        .write_record(CSV_HEADERS)
+        .map_err(|e| e.to_string())?;
+    for task in tasks {
+        let percent = task.percent.to_string();
+        let tags = task.tags.clone().unwrap_or_default().join(", ");
+        writer
+            .write_record([
+                task.id.as_str(),
+                task.text.as_str(),
+                task.status.as_str(),
+                percent.as_str(),
+                task.time.start.as_str(),
+                task.time.end.as_deref().unwrap_or_default(),
+                tags.as_str(),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+// 按日期分组的清单，例如
+// ## 2026-10-19 周一
+// - [x] 写周报 #工作 30%
+fn to_markdown(tasks: &[Task], query: &ExportQuery) -> String {
+    let range = match (query.start.as_deref(), query.end.as_deref()) {
+        (Some(start), Some(end)) => format!(" {} ~ {}", short_date(start), short_date(end)),
+        (Some(start), None) => format!(" {} 起", short_date(start)),
+        (None, Some(end)) => format!(" 截至 {}", short_date(end)),
+        (None, None) => String::new(),
+    };
+    let done = tasks.iter().filter(|task| task.is_done()).count();
+    let mut lines = vec![
+        format!("# 任务{}", range),
+        String::new(),
+        format!("完成 {} / {}", done, tasks.len()),
+    ];
+
+    let mut current_day = None;
+    for task in tasks {
+        let day = task.start_date();
+        if current_day != Some(day) {
+            current_day = Some(day);
+            lines.push(String::new());
+            lines.push(match day {
+                Some(day) => format!("## {} {}", day.format("%Y-%m-%d"), weekday_name(day)),
+                None => "## 未设置日期".to_string(),
+            });
+        }
+
+        let check = if task.is_done() { "x" } else { " " };
+        let mut line = format!("- [{}] {}", check, task.text.replace('\n', " "));
+        for tag in task.tags.iter().flatten() {
+            line.push_str(&format!(" #{}", tag));
+        }
+        if task.percent > 0 {
+            line.push_str(&format!(" {}%", task.percent));
+        }
+        lines.push(line);
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn short_date(date: &str) -> String {
+    date.chars().take(10).collect()
+}
+
+fn weekday_name(day: NaiveDate) -> &'static str {
+    ["周一", "周二", "周三", "周四", "周五", "周六", "周日"]
+        [day.weekday().num_days_from_monday() as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_notion::test_task;
+
+    fn task(text: &str, status: &str, start: &str, tags: &[&str], percent: u32) -> Task {
+        let mut task = test_task(text, status, start);
+        task.tags = Some(tags.iter().map(|tag| tag.to_string()).collect());
+        task.percent = percent;
+        task
+    }
+
+    #[test]
+    fn csv_quotes_text_and_joins_tags() {
+        let tasks = [task(
+            "写周报, \"第 42 周\"",
+            "完成",
+            "2026-10-19",
+            &["工作", "周报"],
+            30,
+        )];
+        let csv = to_csv(&tasks).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "id,text,status,percent,start,end,tags");
+        assert_eq!(
+            lines[1],
+            ",\"写周报, \"\"第 42 周\"\"\",完成,30,2026-10-19,,\"工作, 周报\""
+        );
+    }
+
+    #[test]
+    fn markdown_groups_tasks_by_day() {
+        let tasks = [
+            task("写周报", "完成", "2026-10-19", &["工作"], 30),
+            task("买菜", "未开始", "2026-10-19", &[], 0),
+            task("复盘", "进行中", "2026-10-20", &[], 0),
+            task("读书", "未开始", "", &[], 0),
+        ];
+        let query = ExportQuery {
+            start: Some("2026-10-19".to_string()),
+            end: Some("2026-10-25T23:59:59+08:00".to_string()),
+            ..Default::default()
+        };
+        let expected = [
+            "# 任务 2026-10-19 ~ 2026-10-25",
+            "",
+            "完成 1 / 4",
+            "",
+            "## 2026-10-19 周一",
+            "- [x] 写周报 #工作 30%",
+            "- [ ] 买菜",
+            "",
+            "## 2026-10-20 周二",
+            "- [ ] 复盘",
+            "",
+            "## 未设置日期",
+            "- [ ] 读书",
+            "",
+        ];
+        assert_eq!(to_markdown(&tasks, &query), expected.join("\n"));
+    }
+
+    #[test]
+    fn date_bound_covers_whole_day() {
+        let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+        let bound = date_bound(Some("2026-10-19"), end_of_day).unwrap().unwrap();
+        let bound = DateTime::parse_from_rfc3339(&bound)
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!(
+            bound.naive_local(),
+            NaiveDate::from_ymd_opt(2026, 10, 19)
+                .unwrap()
+                .and_time(end_of_day)
+        );
+
+        // 已经带时间的保持不变
+        assert_eq!(
+            date_bound(Some("2026-10-19T08:00:00+08:00"), end_of_day).unwrap(),
+            Some("2026-10-19T08:00:00+08:00".to_string())
+        );
+        assert_eq!(date_bound(Some(" "), end_of_day).unwrap(), None);
+        assert!(date_bound(Some("10/19"), end_of_day).is_err());
+    }
+
+    #[test]
+    fn output_path_uses_default_name_for_directories() {
+        let dir = std::env::temp_dir().join(format!("task-reminder-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let name = format!("tasks-{}.md", Local::now().format("%Y-%m-%d"));
+        assert_eq!(
+            output_path(dir.to_str().unwrap(), ExportFormat::Markdown).unwrap(),
+            dir.join(name)
+        );
+        let file = dir.join("week.csv");
+        assert_eq!(
+            output_path(file.to_str().unwrap(), ExportFormat::Csv).unwrap(),
+            file
+        );
+        let missing = dir.join("missing").join("week.csv");
+        assert!(output_path(missing.to_str().unwrap(), ExportFormat::Csv).is_err());
+        assert!(output_path("  ", ExportFormat::Json).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,241 @@
+// src-tauri/src/hooks.rs
+// 事件钩子：任务完成、发出提醒、新的一天开始时执行用户配置的本地命令，例如切换忙碌灯、记录到时间追踪工具
+// 事件内容以 JSON 写入 stdin，同时设置环境变量：
+//   TASK_REMINDER_EVENT  事件名，例如 task_completed
+//   TASK_REMINDER_TASK   相关任务的 JSON，没有时为空
+//   TASK_REMINDER_TEXT   任务内容或提醒摘要
+// 命令在单独的进程组中运行，超时后连同它启动的子进程一起结束，输出记录在日志中
+use crate::app_core::TaskReminderCore;
+use crate::reminder::shell_command;
+use crate::task_manager::Task;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+// 日志中每个输出流最多记录的字符数
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+// 正在执行的命令数，命令行退出前等待
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    TaskCompleted,
+    ReminderFired,
+    DayStarted, // 每天第一次启动，或运行中跨过零点
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ShellHook {
+    pub enabled: bool,
+    pub event: HookEvent,
+    pub command: String,
+    pub timeout_secs: u64,
+}
+
+impl Default for ShellHook {
+    fn default() -> Self {
+        ShellHook {
+            enabled: true,
+            event: HookEvent::TaskCompleted,
+            command: String::new(),
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// 在后台执行订阅了该事件的命令，extra 中的字段会合并到事件内容
+pub fn fire(core: &TaskReminderCore, event: HookEvent, task: Option<&Task>, extra: Value) {
+    let hooks: Vec<ShellHook> = match core.load_setting() {
+        Ok(setting) => setting
+            .hooks
+            .into_iter()
+            .filter(|hook| hook.enabled && hook.event == event)
+            .filter(|hook| !hook.command.trim().is_empty())
+            .collect(),
+        Err(e) => {
+            log::error!("load hooks error: {}", e);
+            return;
+        }
+    };
+    if hooks.is_empty() {
+        return;
+    }
+
+    let mut payload = json!({
+        "event": event,
+        "timestamp": Local::now().to_rfc3339(),
+        "task": task,
+    });
+    if let (Some(payload), Value::Object(extra)) = (payload.as_object_mut(), extra) {
+        payload.extend(extra);
+    }
+
+    let event_name = payload["event"].as_str().unwrap_or_default().to_string();
+    let task_json = task
+        .and_then(|task| serde_json::to_string(task).ok())
+        .unwrap_or_default();
+    let text = match (task, payload["text"].as_str()) {
+        (_, Some(text)) => text.to_string(),
+        (Some(task), None) => task.text.clone(),
+        (None, None) => String::new(),
+    };
+    let env = vec![
+        ("TASK_REMINDER_EVENT", event_name),
+        ("TASK_REMINDER_TASK", task_json),
+        ("TASK_REMINDER_TEXT", text),
+    ];
+    let payload = payload.to_string();
+
+    for hook in hooks {
+        let env = env.clone();
+        let payload = payload.clone();
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        std::thread::spawn(move || {
+            run(&hook, &env, &payload);
+            IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/// 等待正在执行的命令结束，用于命令行退出前
+/// 每个命令超时后都会被结束，所以不另设等待时间
+pub fn wait_for_hooks() {
+    while IN_FLIGHT.load(Ordering::SeqCst) > 0 {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn run(hook: &ShellHook, env: &[(&str, String)], payload: &str) {
+    let started = Instant::now();
+    let mut command = shell_command(&hook.command);
+    command
+        .envs(env.iter().map(|(key, value)| (*key, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = match spawn_in_group(&mut command) {
+        Ok(child) => child,
+        Err(e) => {
+            log::error!("hook `{}` spawn error: {:?}", hook.command, e);
+            return;
+        }
+    };
+
+    // 命令不读取 stdin 时写入会失败，忽略
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let status = wait_with_timeout(&mut child, Duration::from_secs(hook.timeout_secs.max(1)));
+    let elapsed = started.elapsed().as_millis();
+    // 命令启动的后台进程可能仍占用输出，最多再等 1 秒
+    let deadline = Instant::now() + Duration::from_secs(1);
+    let receive = |rx: mpsc::Receiver<String>| {
+        rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            .unwrap_or_default()
+    };
+    let stdout = receive(stdout);
+    let stderr = receive(stderr);
+
+    match status {
+        Some(status) if status.success() => log::info!(
+            "hook `{}` finished in {}ms, stdout: {:?}, stderr: {:?}",
+            hook.command,
+            elapsed,
+            truncate(&stdout),
+            truncate(&stderr)
+        ),
+        Some(status) => log::warn!(
+            "hook `{}` failed with {} in {}ms, stdout: {:?}, stderr: {:?}",
+            hook.command,
+            status,
+            elapsed,
+            truncate(&stdout),
+            truncate(&stderr)
+        ),
+        None => log::warn!(
+            "hook `{}` killed after {}s timeout, stdout: {:?}, stderr: {:?}",
+            hook.command,
+            hook.timeout_secs,
+            truncate(&stdout),
+            truncate(&stderr)
+        ),
+    }
+}
+
+// 超时或出错时结束进程并返回 None
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Option<std::process::ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(100)),
+            Ok(None) => break,
+            Err(e) => {
+                log::error!("wait hook error: {:?}", e);
+                break;
+            }
+        }
+    }
+    kill_group(child);
+    let _ = child.wait();
+    None
+}
+
+#[cfg(unix)]
+fn spawn_in_group(command: &mut Command) -> std::io::Result<Child> {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0).spawn()
+}
+
+#[cfg(not(unix))]
+fn spawn_in_group(command: &mut Command) -> std::io::Result<Child> {
+    command.spawn()
+}
+
+// 进程组 id 与 sh 的 pid 相同
+#[cfg(unix)]
+fn kill_group(child: &mut Child) {
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+// Windows 没有进程组，用 taskkill /T 结束进程树
+#[cfg(not(unix))]
+fn kill_group(child: &mut Child) {
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &child.id().to_string()])
+        .output();
+    let _ = child.kill();
+}
+
+fn read_in_background(stream: Option<impl Read + Send + 'static>) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    if let Some(mut stream) = stream {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = stream.read_to_end(&mut buf);
+            let _ = tx.send(String::from_utf8_lossy(&buf).trim().to_string());
+        });
+    }
+    rx
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,522 @@
+// src-tauri/src/http_api.rs
+// 本地 HTTP 接口：供构建脚本、编辑器插件等工具添加和查询任务，默认关闭
+//   GET   /tasks?view=today        查看任务：today / overdue / week / done
+//   POST  /tasks                   添加任务 {"text": "明天下午3点 写周报", "tags": ["工作"], "date": "明天"}
+//   PATCH /tasks/{id}              修改任务 {"text", "percent", "status", "date", "tags"}，只修改传入的字段
+//   POST  /tasks/{id}/complete     完成任务
+//   POST  /snooze                  稍后提醒 {"minutes": 30} 或 {"duration": "1h"}
+//   GET   /openapi.json            接口描述，不需要 token
+// 只监听 127.0.0.1，请求需要带 Authorization: Bearer <token>，token 在设置中生成
+// 返回内容与命令行 --json 的输出相同，见 slash_command::CommandResult
+use crate::app_core::CoreExt;
+use crate::setting::snooze_impl;
+use crate::slash_command::{load_view, parse_slash_command, CommandResult, SlashCommand, TaskView};
+use crate::task_manager::{
+    add_task_impl, is_task_id, load_task_from_notion_impl, update_task_impl, SaveResult, Task,
+    TASK_STATUSES,
+};
+use crate::task_parser::{build_task, parse_date};
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Url};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const OPENAPI: &str = include_str!("../openapi.json");
+
+const MAX_BODY_LEN: u64 = 64 * 1024;
+const MIN_TOKEN_LEN: usize = 16;
+const NOT_FOUND: &str = "任务不存在或已删除";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct HttpApiSetting {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String, // 为空时不启动
+}
+
+impl Default for HttpApiSetting {
+    fn default() -> Self {
+        HttpApiSetting {
+            enabled: false,
+            port: 27121,
+            token: String::new(),
+        }
+    }
+}
+
+struct RunningServer {
+    setting: HttpApiSetting,
+    server: Arc<Server>,
+    thread: JoinHandle<()>,
+}
+
+static RUNNING: Lazy<Mutex<Option<RunningServer>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    OpenApi,
+    List(TaskView),
+    Add,
+    Update(String),
+    Complete(String),
+    Snooze,
+}
+
+#[derive(Deserialize)]
+struct AddBody {
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdateBody {
+    text: Option<String>,
+    percent: Option<u32>,
+    status: Option<String>,
+    date: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct SnoozeBody {
+    minutes: Option<u64>,
+    duration: Option<String>,
+}
+
+/// 按设置启动、重启或停止接口服务，设置没有变化时不做处理
+pub fn apply_http_api(app: &AppHandle, setting: &HttpApiSetting) {
+    let mut running = RUNNING.lock().unwrap();
+    if running.as_ref().map(|r| &r.setting) == Some(setting) {
+        return;
+    }
+    if let Some(old) = running.take() {
+        log::info!("stop http api on port {}", old.setting.port);
+        old.server.unblock();
+        let _ = old.thread.join();
+    }
+
+    if !setting.enabled {
+        return;
+    }
+    if let Err(e) = check_setting(setting) {
+        app.core().warn(e);
+        return;
+    }
+
+    let server = match bind(setting.port) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            log::error!("start http api on port {} error: {}", setting.port, e);
+            app.core().warn(format!(
+                "本地接口启动失败，端口 {} 可能被占用：{}",
+                setting.port, e
+            ));
+            return;
+        }
+    };
+    log::info!("http api listening on 127.0.0.1:{}", setting.port);
+
+    let thread = {
+        let app = app.clone();
+        let server = server.clone();
+        let setting = setting.clone();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                accept(&app, &setting, request);
+            }
+        })
+    };
+    *running = Some(RunningServer {
+        setting: setting.clone(),
+        server,
+        thread,
+    });
+}
+
+// 端口和 token 无效时不启动
+fn check_setting(setting: &HttpApiSetting) -> Result<(), String> {
+    if setting.port == 0 {
+        return Err("本地接口的端口无效，接口未启动".to_string());
+    }
+    if setting.token.len() < MIN_TOKEN_LEN {
+        return Err(format!(
+            "本地接口的 token 至少需要 {} 个字符，接口未启动",
+            MIN_TOKEN_LEN
+        ));
+    }
+    Ok(())
+}
+
+// 重启时旧的监听可能还没有完全关闭，稍等后重试
+fn bind(port: u16) -> Result<Server, String> {
+    let mut result = Server::http(("127.0.0.1", port));
+    for _ in 0..3 {
+        if result.is_ok() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+        result = Server::http(("127.0.0.1", port));
+    }
+    result.map_err(|e| e.to_string())
+}
+
+// 在监听线程中校验请求并读取内容，之后的处理放到异步任务中，避免慢请求阻塞其他请求
+fn accept(app: &AppHandle, setting: &HttpApiSetting, mut request: Request) {
+    let host = header(&request, "Host").unwrap_or_default();
+    if !host_allowed(&host, setting.port) {
+        respond(request, failed(403, "error", "拒绝访问"));
+        return;
+    }
+
+    let Some(route) = route(request.method(), request.url()) else {
+        respond(request, failed(404, "error", "接口不存在"));
+        return;
+    };
+    if route == Route::OpenApi {
+        let response = Response::from_string(OPENAPI).with_header(json_header());
+        let _ = request.respond(response);
+        return;
+    }
+
+    let authorization = header(&request, "Authorization");
+    if !authorized(authorization.as_deref(), &setting.token) {
+        respond(request, failed(401, "auth", "token 无效"));
+        return;
+    }
+
+    let mut body = String::new();
+    if let Err(e) = request
+        .as_reader()
+        .take(MAX_BODY_LEN)
+        .read_to_string(&mut body)
+    {
+        respond(request, failed(400, "error", e.to_string()));
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let response = handle(&app, route, &body).await;
+        log::info!(
+            "http api {} {}: {}",
+            request.method(),
+            request.url(),
+            response.0
+        );
+        respond(request, response);
+    });
+}
+
+// 校验 Host，防止网页通过 DNS 重绑定访问本地接口
+fn host_allowed(host: &str, port: u16) -> bool {
+    let allowed = [format!("127.0.0.1:{}", port), format!("localhost:{}", port)];
+    allowed
+        .iter()
+        .any(|allowed| host.eq_ignore_ascii_case(allowed))
+}
+
+// Authorization: Bearer <token>
+fn authorized(authorization: Option<&str>, token: &str) -> bool {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| constant_time_eq(value.trim(), token))
+}
+
+fn route(method: &Method, url: &str) -> Option<Route> {
+    let url = Url::parse(&format!("http://127.0.0.1{}", url)).ok()?;
+    let segments: Vec<&str> = url.path().trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        (Method::Get, ["openapi.json"]) => Some(Route::OpenApi),
+        (Method::Get, ["tasks"]) => {
+            let view = url
+                .query_pairs()
+                .find(|(key, _)| key == "view")
+                .map(|(_, value)| value.to_string());
+            let view = match view.as_deref() {
+                None | Some("today") => TaskView::Today,
+                Some("overdue") => TaskView::Overdue,
+                Some("week") => TaskView::Week,
+                Some("done") => TaskView::Done,
+                Some(_) => return None,
+            };
+            Some(Route::List(view))
+        }
+        (Method::Post, ["tasks"]) => Some(Route::Add),
+        (Method::Patch, ["tasks", id]) if is_task_id(id) => Some(Route::Update(id.to_string())),
+        (Method::Post, ["tasks", id, "complete"]) if is_task_id(id) => {
+            Some(Route::Complete(id.to_string()))
+        }
+        (Method::Post, ["snooze"]) => Some(Route::Snooze),
+        _ => None,
+    }
+}
+
+async fn handle(app: &AppHandle, route: Route, body: &str) -> (u16, CommandResult) {
+    // 稍后提醒只修改本机的提醒计时，其他接口需要读写 Notion 中的任务
+    if route != Route::Snooze && app.core().auth_info().is_none() {
+        return failed(503, "auth", "未登录 Notion，请先在应用中登录");
+    }
+
+    let result = match route {
+        Route::OpenApi => unreachable!(),
+        Route::List(view) => list(view, app).await,
+        Route::Add => match serde_json::from_str(body) {
+            Ok(body) => add(body, app).await,
+            Err(e) => return failed(400, "add", e.to_string()),
+        },
+        Route::Update(id) => match serde_json::from_str(body) {
+            Ok(body) => update(&id, body, app).await,
+            Err(e) => return failed(400, "update", e.to_string()),
+        },
+        Route::Complete(id) => complete(&id, app).await,
+        Route::Snooze => match serde_json::from_str(body) {
+            Ok(body) => return snooze(body, app),
+            Err(e) => return failed(400, "snooze", e.to_string()),
+        },
+    };
+    result.unwrap_or_else(|e| failed(500, "error", e))
+}
+
+async fn list(view: TaskView, app: &AppHandle) -> Result<(u16, CommandResult), String> {
+    let tasks = load_view(view, app.core()).await?;
+    let mut result = CommandResult::ok("list", format!("共 {} 个任务", tasks.len()));
+    result.tasks = Some(tasks);
+    Ok((200, result))
+}
+
+async fn add(body: AddBody, app: &AppHandle) -> Result<(u16, CommandResult), String> {
+    let mut task = match build_task(&body.text, body.tags, body.date.as_deref(), Local::now()) {
+        Ok(task) => task,
+        Err(e) => return Ok(failed(400, "add", e)),
+    };
+    let result = add_task_impl(&task, app.core()).await?;
+    if !result.success {
+        return Ok(save_failed("add", result));
+    }
+    task.id = result.id.unwrap_or_default();
+    let _ = app.emit("tasks_changed", ());
+
+    let mut result = CommandResult::ok("add", format!("已添加: {}", task.text));
+    result.task = Some(task);
+    Ok((201, result))
+}
+
+async fn update(
+    id: &str,
+    body: UpdateBody,
+    app: &AppHandle,
+) -> Result<(u16, CommandResult), String> {
+    let Some(mut task) = load_task_from_notion_impl(id, app.core()).await? else {
+        return Ok(failed(404, "update", NOT_FOUND));
+    };
+
+    if let Some(text) = body.text {
+        if text.trim().is_empty() {
+            return Ok(failed(400, "update", "任务内容不能为空"));
+        }
+        task.text = text.trim().to_string();
+    }
+    if let Some(percent) = body.percent {
+        if percent > 100 {
+            return Ok(failed(400, "update", "percent 不能超过 100"));
+        }
+        task.percent = percent;
+    }
+    if let Some(status) = body.status {
+        if !TASK_STATUSES.contains(&status.as_str()) {
+            let message = format!(
+                "无法识别的状态: {}，可选: {}",
+                status,
+                TASK_STATUSES.join("、")
+            );
+            return Ok(failed(400, "update", message));
+        }
+        task.status = status;
+    }
+    if let Some(date) = body.date {
+        match parse_date(&date, Local::now()) {
+            Some(time) => task.time = time,
+            None => return Ok(failed(400, "update", format!("无法识别的日期: {}", date))),
+        }
+    }
+    if let Some(tags) = body.tags {
+        task.tags = Some(tags);
+    }
+    save(task, "update", "已修改", app).await
+}
+
+async fn complete(id: &str, app: &AppHandle) -> Result<(u16, CommandResult), String> {
+    let Some(mut task) = load_task_from_notion_impl(id, app.core()).await? else {
+        return Ok(failed(404, "complete", NOT_FOUND));
+    };
+    task.mark_done();
+    save(task, "complete", "已完成", app).await
+}
+
+async fn save(
+    task: Task,
+    action: &str,
+    done_message: &str,
+    app: &AppHandle,
+) -> Result<(u16, CommandResult), String> {
+    let result = update_task_impl(&task, app.core()).await?;
+    if !result.success {
+        return Ok(save_failed(action, result));
+    }
+    let _ = app.emit("tasks_changed", ());
+
+    let mut result = CommandResult::ok(action, format!("{}: {}", done_message, task.text));
+    result.task = Some(task);
+    Ok((200, result))
+}
+
+fn snooze(body: SnoozeBody, app: &AppHandle) -> (u16, CommandResult) {
+    let minutes = match (body.minutes, body.duration) {
+        (Some(minutes), _) => minutes,
+        (None, Some(duration)) => match parse_slash_command(&format!("/snooze {}", duration)) {
+            Ok(SlashCommand::Snooze { minutes }) => minutes,
+            Ok(_) => return failed(400, "snooze", format!("无法识别的时长: {}", duration)),
+            Err(e) => return failed(400, "snooze", e),
+        },
+        (None, None) => return failed(400, "snooze", "缺少 minutes 或 duration"),
+    };
+    if minutes == 0 {
+        return failed(400, "snooze", "时长必须大于 0");
+    }
+    snooze_impl(minutes, app.core());
+    let message = format!("{} 分钟后再提醒", minutes);
+    (200, CommandResult::ok("snooze", message))
+}
+
+fn save_failed(action: &str, result: SaveResult) -> (u16, CommandResult) {
+    let message = result.error.unwrap_or_else(|| "保存失败".to_string());
+    failed(502, action, message)
+}
+
+fn failed(status: u16, action: &str, message: impl Into<String>) -> (u16, CommandResult) {
+    (status, CommandResult::failed(action, message.into()))
+}
+
+fn respond(request: Request, (status, result): (u16, CommandResult)) {
+    let body = serde_json::to_string(&result).unwrap_or_default();
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(json_header());
+    if let Err(e) = request.respond(response) {
+        log::warn!("http api respond error: {:?}", e);
+    }
+}
+
+fn header(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str().to_string())
+}
+
+fn json_header() -> Header {
+    Header::from_bytes("Content-Type", "application/json; charset=utf-8").unwrap()
+}
+
+// 比较 token 时不因提前结束泄露匹配的长度
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d";
+
+    #[test]
+    fn routes_requests() {
+        let cases = [
+            (Method::Get, "/openapi.json", Some(Route::OpenApi)),
+            (Method::Get, "/tasks", Some(Route::List(TaskView::Today))),
+            (
+                Method::Get,
+                "/tasks?view=week",
+                Some(Route::List(TaskView::Week)),
+            ),
+            (
+                Method::Get,
+                "/tasks/?view=done",
+                Some(Route::List(TaskView::Done)),
+            ),
+            (Method::Get, "/tasks?view=someday", None),
+            (Method::Post, "/tasks", Some(Route::Add)),
+            (
+                Method::Patch,
+                &format!("/tasks/{}", ID),
+                Some(Route::Update(ID.to_string())),
+            ),
+            (
+                Method::Post,
+                &format!("/tasks/{}/complete", ID),
+                Some(Route::Complete(ID.to_string())),
+            ),
+            (Method::Patch, "/tasks/../snooze", None),
+            (Method::Post, "/tasks/1a2b3c4d/complete", None),
+            (Method::Post, "/snooze", Some(Route::Snooze)),
+            (Method::Get, "/snooze", None),
+            (Method::Delete, &format!("/tasks/{}", ID), None),
+        ];
+        for (method, url, expected) in cases {
+            assert_eq!(route(&method, url), expected, "{} {}", method, url);
+        }
+    }
+
+    #[test]
+    fn allows_only_local_hosts() {
+        assert!(host_allowed("127.0.0.1:27121", 27121));
+        assert!(host_allowed("LOCALHOST:27121", 27121));
+        assert!(!host_allowed("localhost:8080", 27121));
+        assert!(!host_allowed("evil.example:27121", 27121));
+        assert!(!host_allowed("127.0.0.1", 27121));
+        assert!(!host_allowed("", 27121));
+    }
+
+    #[test]
+    fn checks_bearer_token() {
+        let token = "0123456789abcdef";
+        assert!(authorized(Some("Bearer 0123456789abcdef"), token));
+        assert!(authorized(Some("Bearer 0123456789abcdef "), token));
+        assert!(!authorized(Some("Bearer 0123456789abcdeF"), token));
+        assert!(!authorized(Some("Bearer 0123456789abcde"), token));
+        assert!(!authorized(Some("Basic 0123456789abcdef"), token));
+        assert!(!authorized(None, token));
+
+        assert!(constant_time_eq("", ""));
+        assert!(constant_time_eq("token", "token"));
+        assert!(!constant_time_eq("token", "tokem"));
+        assert!(!constant_time_eq("token", "token2"));
+    }
+
+    #[test]
+    fn refuses_short_token_and_port_zero() {
+        let mut setting = HttpApiSetting {
+            enabled: true,
+            port: 27121,
+            token: "0123456789abcde".to_string(),
+        };
+        assert!(check_setting(&setting).is_err());
+        setting.token.push('f');
+        assert!(check_setting(&setting).is_ok());
+        setting.port = 0;
+        assert!(check_setting(&setting).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+// src-tauri/src/idle.rs
+// 检测用户空闲和系统休眠唤醒，离开期间暂停提醒
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IdleSetting {
+    pub pause_when_idle: bool, // 空闲时暂停提醒计时
+    pub idle_minutes: u64,     // 无操作多少分钟视为空闲
+    pub welcome_back: bool,    // 休眠唤醒或长时间离开后弹出今日任务
+    pub welcome_after: u64,    // 离开多少分钟后才弹出欢迎回来
+}
+
+impl Default for IdleSetting {
+    fn default() -> Self {
+        IdleSetting {
+            pause_when_idle: true,
+            idle_minutes: 5,
+            welcome_back: true,
+            welcome_after: 60,
+        }
+    }
+}
+
+impl IdleSetting {
+    // 当前是否处于空闲状态，无法获取空闲时间时视为不空闲
+    pub fn is_idle(&self) -> bool {
+        self.pause_when_idle
+            && idle_seconds()
+                .map(|secs| secs >= self.idle_minutes * 60)
+                .unwrap_or(false)
+    }
+
+    // 离开时间足够长时弹出欢迎回来
+    pub fn should_welcome(&self, away: Duration) -> bool {
+        self.welcome_back && away.as_secs() >= self.welcome_after * 60
+    }
+}
+
+// 通过墙上时间的跳变检测休眠唤醒
+// 两次检查之间墙上时间前进远超预期，说明期间系统挂起过
+pub struct ResumeDetector {
+    last: SystemTime,
+}
+
+impl Default for ResumeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResumeDetector {
+    // 两次检查之间超过该时间视为休眠
+    const THRESHOLD: Duration = Duration::from_secs(60);
+
+    pub fn new() -> Self {
+        ResumeDetector {
+            last: SystemTime::now(),
+        }
+    }
+
+    // 返回休眠的时长
+    pub fn check(&mut self) -> Option<Duration> {
+        self.check_at(SystemTime::now())
+    }
+
+    fn check_at(&mut self, now: SystemTime) -> Option<Duration> {
+        let gap = now.duration_since(self.last).unwrap_or_default();
+        self.last = now;
+        if gap > Self::THRESHOLD {
+            Some(gap)
+        } else {
+            None
+        }
+    }
+}
+
+// 用户无操作的秒数
+pub fn idle_seconds() -> Option<u64> {
+    if cfg!(target_os = "macos") {
+        macos_idle_seconds()
+    } else if cfg!(target_os = "linux") {
+        x11_idle_seconds().or_else(logind_idle_seconds)
+    } else {
+        None
+    }
+}
+
+// macOS: IOHIDSystem 的 HIDIdleTime，单位纳秒
+fn macos_idle_seconds() -> Option<u64> {
+    let output = Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let line = text.lines().find(|line| line.contains("\"HIDIdleTime\""))?;
+    let nanos: u64 = line.rsplit('=').next()?.trim().parse().ok()?;
+    Some(nanos / 1_000_000_000)
+}
+
+// Linux X11: 通过 MIT-SCREEN-SAVER 扩展查询，单位毫秒
+#[cfg(target_os = "linux")]
+fn x11_idle_seconds() -> Option<u64> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use x11rb::connection::Connection;
+    use x11rb::protocol::screensaver::ConnectionExt;
+
+    // X 服务器不支持 screensaver 扩展时只提示一次，之后不再尝试
+    static UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+    std::env::var_os("DISPLAY")?;
+    if UNSUPPORTED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let (conn, screen) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots.get(screen)?.root;
+    match conn
+        .screensaver_query_info(root)
+        .map_err(|e| e.to_string())
+        .and_then(|cookie| cookie.reply().map_err(|e| e.to_string()))
+    {
+        Ok(info) => Some(u64::from(info.ms_since_user_input) / 1000),
+        Err(e) => {
+            UNSUPPORTED.store(true, Ordering::Relaxed);
+            log::warn!("query X11 screensaver info error: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn x11_idle_seconds() -> Option<u64> {
+    None
+}
+
+// Linux logind: 会话的 IdleHint / IdleSinceHint（微秒时间戳）
+fn logind_idle_seconds() -> Option<u64> {
+    let session = std::env::var("XDG_SESSION_ID").ok()?;
+    let output = Command::new("loginctl")
+        .args([
+            "show-session",
+            &session,
+            "-p",
+            "IdleHint",
+            "-p",
+            "IdleSinceHint",
+        ])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+
+    let mut idle = false;
+    let mut since = 0u64;
+    for line in text.lines() {
+        match line.split_once('=') {
+            Some(("IdleHint", value)) => idle = value == "yes",
+            Some(("IdleSinceHint", value)) => since = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    if !idle || since == 0 {
+        return Some(0);
+    }
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?
+        .as_micros() as u64;
+    Some(now.saturating_sub(since) / 1_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welcomes_only_after_long_absence() {
+        let setting = IdleSetting::default();
+        assert!(!setting.should_welcome(Duration::from_secs(59 * 60)));
+        assert!(setting.should_welcome(Duration::from_secs(60 * 60)));
+
+        let setting = IdleSetting {
+            welcome_back: false,
+            ..IdleSetting::default()
+        };
+        assert!(!setting.should_welcome(Duration::from_secs(24 * 3600)));
+    }
+
+    #[test]
+    fn detects_resume_from_clock_jump() {
+        let start = SystemTime::now();
+        let mut clock = ResumeDetector { last: start };
+
+        // 正常的检查间隔
+        let now = start + Duration::from_secs(1);
+        assert_eq!(clock.check_at(now), None);
+        let now = now + ResumeDetector::THRESHOLD;
+        assert_eq!(clock.check_at(now), None);
+
+        // 墙上时间跳变，返回休眠时长
+        let now = now + Duration::from_secs(3600);
+        assert_eq!(clock.check_at(now), Some(Duration::from_secs(3600)));
+        assert_eq!(clock.check_at(now + Duration::from_secs(1)), None);
+
+        // 时间回拨不视为休眠
+        assert_eq!(clock.check_at(start), None);
+        assert_eq!(clock.check_at(start + Duration::from_secs(1)), None);
+    }
+}
@@ -0,0 +1,724 @@
+// src-tauri/src/import.rs
+// 导入任务：支持 CSV（可指定列名）、todo.txt 和导出的 JSON，便于新成员迁移已有的任务清单
+// 先用 dry_run 预览解析结果，标题和日期都相同的任务视为重复，不会再次创建
+// 任务分批写入 Notion，每批之间暂停，避免触发频率限制
+//   todo.txt: x 2026-10-19 (A) 2026-10-18 写周报 +工作 @电脑 due:2026-10-20
+//   优先级 (A) (B) (C) 对应权重 90 70 50，其余为 30；+项目 和 @场景 作为标签
+use crate::app_core::{CoreExt, TaskReminderCore};
+use crate::export::{query_tasks, ExportQuery};
+use crate::task_manager::{add_task_to_notion_impl, Task, TaskList, Time};
+use crate::task_parser::{format_time, parse_date};
+use crate::tray::spawn_refresh_tray_menu;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter};
+use tokio::time::{sleep, Duration};
+
+const BATCH_SIZE: usize = 10;
+const BATCH_PAUSE: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Csv,
+    TodoTxt,
+    Json,
+}
+
+// CSV 中各字段对应的列名，不区分大小写，默认与导出的列名相同，为空时不导入该字段
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct CsvMapping {
+    pub text: String,
+    pub date: String,
+    pub status: String,
+    pub percent: String,
+    pub tags: String,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        CsvMapping {
+            text: "text".to_string(),
+            date: "start".to_string(),
+            status: "status".to_string(),
+            percent: "percent".to_string(),
+            tags: "tags".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ImportOptions {
+    pub format: ImportFormat,
+    #[serde(default)]
+    pub mapping: CsvMapping,
+    #[serde(default)]
+    pub dry_run: bool, // 只解析和检查重复，不创建任务
+}
+
+#[derive(Serialize)]
+pub struct ImportItem {
+    pub line: usize, // 在文件中的行号，JSON 为序号
+    pub task: Task,
+    pub duplicate: bool, // 与已有任务或文件中前面的任务重复
+    pub created: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // 创建失败的原因
+}
+
+#[derive(Serialize, Default)]
+pub struct ImportResult {
+    pub dry_run: bool,
+    pub items: Vec<ImportItem>,
+    pub errors: Vec<String>, // 无法解析的行
+    pub created: usize,
+    pub duplicates: usize,
+    pub failed: usize,
+}
+
+#[derive(Serialize, Clone)]
+struct ImportProgress {
+    done: usize,
+    total: usize,
+}
+
+// 导出的 JSON 为任务数组，也接受 {"tasks": [...]}
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonTasks {
+    List(Vec<Task>),
+    Wrapped(TaskList),
+}
+
+type Parsed = Vec<(usize, Result<Task, String>)>;
+
+#[tauri::command]
+pub async fn import_tasks(
+    path: String,
+    options: ImportOptions,
+    app: AppHandle,
+) -> Result<ImportResult, String> {
+    if app.core().auth_info().is_none() {
+        return Err("未登录 Notion，请先登录".to_string());
+    }
+    let content =
+        std::fs::read_to_string(path.trim()).map_err(|e| format!("读取 {} 失败: {}", path, e))?;
+    let parsed = parse_content(&content, &options, Local::now().date_naive())?;
+
+    let mut result = ImportResult {
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    for (line, task) in parsed {
+        match task {
+            Ok(task) => result.items.push(ImportItem {
+                line,
+                task,
+                duplicate: false,
+                created: false,
+                error: None,
+            }),
+            Err(e) => result.errors.push(format!("第 {} 行: {}", line, e)),
+        }
+    }
+
+    mark_duplicates(&mut result.items, app.core()).await?;
+    result.duplicates = result.items.iter().filter(|item| item.duplicate).count();
+    if options.dry_run {
+        return Ok(result);
+    }
+
+    create_tasks(&mut result, &app).await;
+    log::info!(
+        "import tasks: created {}, duplicates {}, failed {}, invalid {}",
+        result.created,
+        result.duplicates,
+        result.failed,
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+// 查询导入任务日期范围内的所有已有任务（分页读取），标题和日期都相同时视为重复
+async fn mark_duplicates(items: &mut [ImportItem], core: &TaskReminderCore) -> Result<(), String> {
+    let dates: Vec<NaiveDate> = items
+        .iter()
+        .filter_map(|item| item.task.start_date())
+        .collect();
+    let (Some(first), Some(last)) = (dates.iter().min(), dates.iter().max()) else {
+        return Ok(());
+    };
+
+    let query = ExportQuery {
+        start: Some(first.format("%Y-%m-%d").to_string()),
+        end: Some(last.format("%Y-%m-%d").to_string()),
+        status: Some("0".to_string()),
+        tags: vec![],
+    };
+    let existing = query_tasks(&query, core).await?;
+    let mut seen: HashSet<(String, Option<NaiveDate>)> = existing.iter().map(dedup_key).collect();
+    for item in items.iter_mut() {
+        item.duplicate = !seen.insert(dedup_key(&item.task));
+    }
+    Ok(())
+}
+
+// 导入不逐个发送 webhook 和刷新托盘，全部完成后刷新一次
+async fn create_tasks(result: &mut ImportResult, app: &AppHandle) {
+    let total = result.items.iter().filter(|item| !item.duplicate).count();
+    let mut done = 0;
+    let pending = result.items.iter_mut().filter(|item| !item.duplicate);
+    for (i, item) in pending.enumerate() {
+        if i > 0 && i % BATCH_SIZE == 0 {
+            let _ = app.emit("import_progress", ImportProgress { done, total });
+            sleep(BATCH_PAUSE).await;
+        }
+        match add_task_to_notion_impl(&item.task, app.core()).await {
+            Ok(saved) if saved.success => {
+                item.task.id = saved.id.unwrap_or_default();
+                item.created = true;
+                result.created += 1;
+            }
+            Ok(saved) => {
+                item.error = Some(saved.error.unwrap_or_else(|| "创建失败".to_string()));
+                result.failed += 1;
+            }
+            Err(e) => {
+                item.error = Some(e);
+                result.failed += 1;
+            }
+        }
+        done += 1;
+    }
+    let _ = app.emit("import_progress", ImportProgress { done, total });
+
+    if result.created > 0 {
+        spawn_refresh_tray_menu(app);
+        let _ = app.emit("tasks_changed", ());
+    }
+}
+
+fn dedup_key(task: &Task) -> (String, Option<NaiveDate>) {
+    (task.text.trim().to_lowercase(), task.start_date())
+}
+
+// 按格式解析文件内容，没有日期的任务放在 today
+fn parse_content(
+    content: &str,
+    options: &ImportOptions,
+    today: NaiveDate,
+) -> Result<Parsed, String> {
+    // Excel 导出的 CSV 带有 BOM
+    let content = content.trim_start_matches('\u{feff}');
+    match options.format {
+        ImportFormat::Csv => parse_csv(content, &options.mapping, today),
+        ImportFormat::TodoTxt => Ok(parse_todo_txt(content, today)),
+        ImportFormat::Json => parse_json(content, today),
+    }
+}
+
+fn parse_csv(content: &str, mapping: &CsvMapping, today: NaiveDate) -> Result<Parsed, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let column = |name: &str| {
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+    };
+    let Some(text_column) = column(&mapping.text) else {
+        return Err(format!("找不到任务内容列: {}", mapping.text));
+    };
+    let date_column = column(&mapping.date);
+    let status_column = column(&mapping.status);
+    let percent_column = column(&mapping.percent);
+    let tags_column = column(&mapping.tags);
+
+    let mut parsed = vec![];
+    for (i, record) in reader.records().enumerate() {
+        // 表头为第 1 行
+        let mut line = i + 2;
+        let task = record.map_err(|e| e.to_string()).and_then(|record| {
+            if let Some(position) = record.position() {
+                line = position.line() as usize;
+            }
+            let field = |column: Option<usize>| column.and_then(|c| record.get(c)).unwrap_or("");
+            let text = field(Some(text_column));
+            if text.is_empty() {
+                return Err("任务内容为空".to_string());
+            }
+            let percent = field(percent_column).trim_end_matches('%').trim();
+            let percent = if percent.is_empty() {
+                0
+            } else {
+                percent
+                    .parse::<f64>()
+                    .map(|p| p.clamp(0.0, 100.0) as u32)
+                    .map_err(|_| format!("无法识别的权重: {}", percent))?
+            };
+            Ok(Task {
+                id: String::new(),
+                text: text.to_string(),
+                percent,
+                status: normalize_status(field(status_column)),
+                time: parse_when(field(date_column), today)?,
+                tags: Some(split_tags(field(tags_column))),
+            })
+        });
+        parsed.push((line, task));
+    }
+    Ok(parsed)
+}
+
+fn parse_todo_txt(content: &str, today: NaiveDate) -> Parsed {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, parse_todo_line(line, today)))
+        .collect()
+}
+
+fn parse_todo_line(line: &str, today: NaiveDate) -> Result<Task, String> {
+    let ymd = |word: &str| NaiveDate::parse_from_str(word, "%Y-%m-%d").ok();
+    let mut words = line.split_whitespace().peekable();
+
+    // 已完成的任务以 "x " 开头，之后可能是完成日期
+    let done = words.next_if_eq(&"x").is_some();
+    if done {
+        words.next_if(|word| ymd(word).is_some());
+    }
+    let mut priority = words
+        .next_if(|word| is_priority(word))
+        .and_then(|word| word.chars().nth(1));
+    let created = words.next_if(|word| ymd(word).is_some()).and_then(ymd);
+
+    let mut text = vec![];
+    let mut tags: Vec<String> = vec![];
+    let mut due = None;
+    for word in words {
+        let tag = word
+            .strip_prefix('+')
+            .or_else(|| word.strip_prefix('@'))
+            .filter(|tag| !tag.is_empty());
+        if let Some(tag) = tag {
+            if !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        } else if let Some(date) = word.strip_prefix("due:") {
+            due = Some(ymd(date).ok_or_else(|| format!("无法识别的日期: {}", date))?);
+        } else if let Some(value) = word.strip_prefix("pri:") {
+            priority = value.chars().next();
+        } else {
+            text.push(word);
+        }
+    }
+    if text.is_empty() {
+        return Err("任务内容为空".to_string());
+    }
+
+    let percent = match priority.map(|p| p.to_ascii_uppercase()) {
+        Some('A') => 90,
+        Some('B') => 70,
+        Some('C') => 50,
+        Some(_) => 30,
+        None => 0,
+    };
+    let date = due.or(created).unwrap_or(today);
+    Ok(Task {
+        id: String::new(),
+        text: text.join(" "),
+        percent,
+        status: if done { "完成" } else { "未开始" }.to_string(),
+        time: all_day(date),
+        tags: Some(tags),
+    })
+}
+
+// 例如 (A)
+fn is_priority(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    bytes.len() == 3 && bytes[0] == b'(' && bytes[1].is_ascii_uppercase() && bytes[2] == b')'
+}
+
+fn parse_json(content: &str, today: NaiveDate) -> Result<Parsed, String> {
+    let tasks = match serde_json::from_str(content).map_err(|e| e.to_string())? {
+        JsonTasks::List(tasks) => tasks,
+        JsonTasks::Wrapped(list) => list.tasks,
+    };
+    Ok(tasks
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut task)| {
+            // id 属于原来的数据库，导入时重新创建
+            task.id = String::new();
+            task.text = task.text.trim().to_string();
+            if task.text.is_empty() {
+                return (i + 1, Err("任务内容为空".to_string()));
+            }
+            task.percent = task.percent.min(100);
+            if task.status.trim().is_empty() {
+                task.status = "未开始".to_string();
+            }
+            if task.time.start.trim().is_empty() {
+                task.time = all_day(today);
+            }
+            (i + 1, Ok(task))
+        })
+        .collect())
+}
+
+// 支持 RFC 3339、"2026-10-19 15:00"、"2026-10-19" 和自然语言，例如 "明天下午3点"，为空时为今天
+fn parse_when(value: &str, today: NaiveDate) -> Result<Time, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(all_day(today));
+    }
+    if DateTime::parse_from_rfc3339(value).is_ok() {
+        return Ok(Time {
+            start: value.to_string(),
+            end: None,
+            time_zone: None,
+        });
+    }
+    for format in ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y/%m/%d %H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            let local = time
+                .and_local_timezone(Local)
+                .earliest()
+                .ok_or_else(|| format!("无法识别的时间: {}", value))?;
+            return Ok(Time {
+                start: format_time(&local),
+                end: None,
+                time_zone: None,
+            });
+        }
+    }
+    for format in ["%Y-%m-%d", "%Y/%m/%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return Ok(all_day(date));
+        }
+    }
+    parse_date(value, Local::now()).ok_or_else(|| format!("无法识别的日期: {}", value))
+}
+
+fn all_day(date: NaiveDate) -> Time {
+    Time {
+        start: date.format("%Y-%m-%d").to_string(),
+        end: None,
+        time_zone: None,
+    }
+}
+
+// 常见的完成、未开始写法转换为 notion 模板中的状态，其他状态原样保留
+fn normalize_status(value: &str) -> String {
+    match value.trim().to_lowercase().as_str() {
+        "done" | "完成" | "已完成" | "x" | "completed" | "yes" | "true" => "完成".to_string(),
+        "" | "todo" | "未开始" | "not started" | "no" | "false" => "未开始".to_string(),
+        "doing" | "进行中" | "in progress" => "进行中".to_string(),
+        _ => value.trim().to_string(),
+    }
+}
+
+fn split_tags(value: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+    for tag in value.split([',', ';', '，']) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{export_content, ExportFormat};
+    use crate::mock_notion::{test_task, MockNotion};
+    use chrono::Duration as DateDuration;
+    use tauri::async_runtime::block_on;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+    }
+
+    fn options(format: ImportFormat, mapping: CsvMapping) -> ImportOptions {
+        ImportOptions {
+            format,
+            mapping,
+            dry_run: true,
+        }
+    }
+
+    // 内容、状态、权重、开始时间、标签
+    fn summary(task: &Task) -> (String, String, u32, String, Vec<String>) {
+        (
+            task.text.clone(),
+            task.status.clone(),
+            task.percent,
+            task.time.start.clone(),
+            task.tags.clone().unwrap_or_default(),
+        )
+    }
+
+    // Task 没有实现 Debug，不能用 unwrap_err
+    fn error<T>(result: Result<T, impl ToString>) -> String {
+        match result {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_todo_txt_lines() {
+        let task = parse_todo_line(
+            "x 2026-10-19 (A) 2026-10-18 写周报 +工作 @电脑 due:2026-10-20",
+            today(),
+        )
+        .unwrap();
+        assert_eq!(
+            summary(&task),
+            (
+                "写周报".to_string(),
+                "完成".to_string(),
+                90,
+                "2026-10-20".to_string(),
+                tags(&["工作", "电脑"])
+            )
+        );
+
+        let cases = [
+            // 输入，任务内容，状态，权重，开始日期，标签
+            (
+                "(B) 2026-10-18 买菜",
+                "买菜",
+                "未开始",
+                70,
+                "2026-10-18",
+                vec![],
+            ),
+            (
+                "复盘 pri:C +工作 +工作",
+                "复盘",
+                "未开始",
+                50,
+                "2026-10-19",
+                tags(&["工作"]),
+            ),
+            ("(D) 读书", "读书", "未开始", 30, "2026-10-19", vec![]),
+            (
+                "x-ray 拍片",
+                "x-ray 拍片",
+                "未开始",
+                0,
+                "2026-10-19",
+                vec![],
+            ),
+        ];
+        for (line, text, status, percent, start, tags) in cases {
+            let task = parse_todo_line(line, today()).unwrap();
+            assert_eq!(
+                summary(&task),
+                (
+                    text.to_string(),
+                    status.to_string(),
+                    percent,
+                    start.to_string(),
+                    tags
+                ),
+                "{}",
+                line
+            );
+        }
+
+        assert_eq!(
+            error(parse_todo_line("写周报 due:明天", today())),
+            "无法识别的日期: 明天"
+        );
+        assert_eq!(
+            error(parse_todo_line("x 2026-10-19 +工作", today())),
+            "任务内容为空"
+        );
+
+        // 空行不算任务，但行号保持不变
+        let parsed = parse_todo_txt("写周报\n\n买菜\n", today());
+        let lines: Vec<usize> = parsed.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [1, 3]);
+    }
+
+    #[test]
+    fn parses_csv_with_custom_columns() {
+        let content = "\u{feff}Title,Due,Done,Weight,Labels\n\
+            写周报,2026-10-20,yes,30%,\"#工作, 周报\"\n\
+            ,2026-10-20,,,\n\
+            买菜,,,abc,\n\
+            读书,2026-10-21 15:00,doing,150%,\n";
+        let mapping = CsvMapping {
+            text: "title".to_string(),
+            date: "due".to_string(),
+            status: "done".to_string(),
+            percent: "weight".to_string(),
+            tags: "labels".to_string(),
+        };
+        let parsed = parse_content(content, &options(ImportFormat::Csv, mapping), today()).unwrap();
+        let lines: Vec<usize> = parsed.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [2, 3, 4, 5]);
+
+        assert_eq!(
+            summary(parsed[0].1.as_ref().unwrap()),
+            (
+                "写周报".to_string(),
+                "完成".to_string(),
+                30,
+                "2026-10-20".to_string(),
+                tags(&["工作", "周报"])
+            )
+        );
+        assert_eq!(error(parsed[1].1.as_ref()), "任务内容为空");
+        assert_eq!(error(parsed[2].1.as_ref()), "无法识别的权重: abc");
+
+        let task = parsed[3].1.as_ref().unwrap();
+        assert_eq!((task.status.as_str(), task.percent), ("进行中", 100));
+        let start = DateTime::parse_from_rfc3339(&task.time.start).unwrap();
+        assert_eq!(
+            start.with_timezone(&Local).naive_local().to_string(),
+            "2026-10-21 15:00:00"
+        );
+    }
+
+    #[test]
+    fn csv_requires_text_column() {
+        let content = "Title,Due\n写周报,2026-10-20\n";
+        let result = parse_content(
+            content,
+            &options(ImportFormat::Csv, CsvMapping::default()),
+            today(),
+        );
+        assert_eq!(error(result), "找不到任务内容列: text");
+    }
+
+    #[test]
+    fn parses_dates_statuses_and_tags() {
+        assert_eq!(parse_when("", today()).unwrap().start, "2026-10-19");
+        assert_eq!(
+            parse_when("2026-10-20T15:00:00+08:00", today())
+                .unwrap()
+                .start,
+            "2026-10-20T15:00:00+08:00"
+        );
+        assert_eq!(
+            parse_when("2026/10/20", today()).unwrap().start,
+            "2026-10-20"
+        );
+        let tomorrow = Local::now().date_naive() + DateDuration::days(1);
+        assert_eq!(
+            parse_when("明天", today()).unwrap().start,
+            tomorrow.format("%Y-%m-%d").to_string()
+        );
+        assert_eq!(
+            error(parse_when("someday", today())),
+            "无法识别的日期: someday"
+        );
+
+        let statuses = [
+            ("Done", "完成"),
+            (" 已完成 ", "完成"),
+            ("x", "完成"),
+            ("", "未开始"),
+            ("TODO", "未开始"),
+            ("In Progress", "进行中"),
+            ("搁置", "搁置"),
+        ];
+        for (value, status) in statuses {
+            assert_eq!(normalize_status(value), status, "{}", value);
+        }
+
+        assert_eq!(
+            split_tags("#工作, 周报；家务;工作，学习"),
+            tags(&["工作", "周报；家务", "学习"])
+        );
+        assert_eq!(split_tags(" , "), tags(&[]));
+    }
+
+    #[test]
+    fn imports_exported_files() {
+        let mut report = test_task("写周报, \"第 42 周\"", "完成", "2026-10-19T15:00:00+08:00");
+        report.id = "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d".to_string();
+        report.percent = 30;
+        report.tags = Some(tags(&["工作", "周报"]));
+        let exported = [report, test_task("买菜", "未开始", "2026-10-20")];
+        let expected: Vec<_> = exported.iter().map(summary).collect();
+
+        for (export_format, import_format) in [
+            (ExportFormat::Csv, ImportFormat::Csv),
+            (ExportFormat::Json, ImportFormat::Json),
+        ] {
+            let content =
+                export_content(&exported, export_format, &ExportQuery::default()).unwrap();
+            let parsed = parse_content(
+                &content,
+                &options(import_format, CsvMapping::default()),
+                today(),
+            )
+            .unwrap();
+            let imported: Vec<_> = parsed
+                .iter()
+                .map(|(_, task)| task.as_ref().unwrap())
+                .collect();
+            assert!(imported.iter().all(|task| task.id.is_empty()));
+            let imported: Vec<_> = imported.into_iter().map(summary).collect();
+            assert_eq!(imported, expected, "{:?}", export_format);
+        }
+    }
+
+    #[test]
+    fn marks_existing_and_repeated_tasks_as_duplicates() {
+        let notion = MockNotion::start();
+        let (core, _) = notion.core();
+        // 每次只返回一个页面，重复的任务在第二页
+        notion.set_page_size(1);
+        notion.add_page(&test_task("买菜", "未开始", "2026-10-20"));
+        notion.add_page(&test_task("写周报", "完成", "2026-10-19"));
+
+        let mut items: Vec<ImportItem> = [
+            (" 写周报 ", "2026-10-19"),
+            ("写周报", "2026-10-20"),
+            ("读书", "2026-10-21"),
+            ("读书", "2026-10-21"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (text, start))| ImportItem {
+            line: i + 1,
+            task: test_task(text, "未开始", start),
+            duplicate: false,
+            created: false,
+            error: None,
+        })
+        .collect();
+
+        block_on(mark_duplicates(&mut items, &core)).unwrap();
+        let duplicates: Vec<bool> = items.iter().map(|item| item.duplicate).collect();
+        assert_eq!(duplicates, [true, false, false, true]);
+        let queries = notion
+            .requests()
+            .iter()
+            .filter(|r| r.ends_with("/query"))
+            .count();
+        assert_eq!(queries, 2);
+    }
+}
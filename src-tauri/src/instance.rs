@@ -0,0 +1,114 @@
+// src-tauri/src/instance.rs
+// 单实例：再次启动时把参数转发给正在运行的程序处理，避免出现两个托盘图标和两个提醒循环
+//   task-reminder --show                显示主窗口
+//   task-reminder --add "明天下午3点 写周报"  添加任务
+// 命令行子命令（见 cli）不经过这里，可以和界面同时运行
+use crate::app_core::CoreExt;
+use crate::task_manager::{add_task_impl, Task};
+use crate::task_parser::parse_task_text;
+use crate::window_manager::show_popup;
+
+use chrono::Local;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+#[derive(Debug, PartialEq)]
+pub enum LaunchAction {
+    Show,
+    Add(String),
+}
+
+/// 解析启动参数（不含程序路径），忽略无法识别的参数，例如开机启动时传入的参数
+pub fn parse_launch_args(args: &[String]) -> Vec<LaunchAction> {
+    let mut actions = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--show" => actions.push(LaunchAction::Show),
+            "--add" => match args.next() {
+                Some(text) if !text.trim().is_empty() => {
+                    actions.push(LaunchAction::Add(text.trim().to_string()))
+                }
+                _ => log::warn!("--add requires task text"),
+            },
+            other => match other.strip_prefix("--add=") {
+                Some(text) if !text.trim().is_empty() => {
+                    actions.push(LaunchAction::Add(text.trim().to_string()))
+                }
+                _ => log::info!("ignore launch arg: {}", other),
+            },
+        }
+    }
+    actions
+}
+
+/// 程序已在运行时再次启动，由运行中的程序处理新进程的参数
+/// 没有参数时（例如再次点击图标）显示主窗口
+pub fn on_second_instance(app: &AppHandle, args: Vec<String>) {
+    log::info!("second instance launched: {:?}", args);
+    let actions = parse_launch_args(args.get(1..).unwrap_or_default());
+    if actions.is_empty() {
+        show_main_window(app);
+        return;
+    }
+    handle_actions(app, actions);
+}
+
+/// 首次启动时处理自己的参数，需要在主窗口创建之后调用
+pub fn handle_launch_args(app: &AppHandle) {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    handle_actions(app, parse_launch_args(&args));
+}
+
+fn handle_actions(app: &AppHandle, actions: Vec<LaunchAction>) {
+    for action in actions {
+        match action {
+            LaunchAction::Show => show_main_window(app),
+            LaunchAction::Add(text) => {
+                let task = parse_task_text(&text, Local::now()).task;
+                if task.text.is_empty() {
+                    continue;
+                }
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    add_task_with_feedback(&app, task).await;
+                });
+            }
+        }
+    }
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        show_popup(app, &window);
+    }
+}
+
+/// 添加任务并用系统通知告知结果，用于启动参数、链接等没有界面反馈的入口
+pub async fn add_task_with_feedback(app: &AppHandle, task: Task) {
+    let message = match add_task_impl(&task, app.core()).await {
+        Ok(result) if result.success => {
+            let _ = app.emit("tasks_changed", ());
+            format!("已添加: {}", task.text)
+        }
+        Ok(result) => format!(
+            "添加失败: {}",
+            result.error.or(result.status).unwrap_or_default()
+        ),
+        Err(e) => format!("添加失败: {}", e),
+    };
+    log::info!("add task: {}", message);
+    notify(app, message);
+}
+
+pub fn notify(app: &AppHandle, message: String) {
+    let result = app
+        .notification()
+        .builder()
+        .title("PUT FIRST THINGS FIRST")
+        .body(message)
+        .show();
+    if let Err(e) = result {
+        log::error!("show notification error: {:?}", e);
+    }
+}
@@ -0,0 +1,409 @@
+// src-tauri/src/ipc.rs
+// 本地脚本接口：运行时目录下的 Unix 套接字，每行一个 JSON-RPC 2.0 请求，返回一行结果
+// 方法与界面调用的命令相同：load_tasks、add_task、update_task、set_remind_later、load_tags，
+// 另有 load_view 按视图查询，便于状态栏显示今日剩余任务；snooze 按分钟稍后提醒，命令行也使用它
+//   echo '{"jsonrpc":"2.0","id":1,"method":"load_view","params":{"view":"today"}}' \
+//     | socat - UNIX-CONNECT:$XDG_RUNTIME_DIR/task-reminder/task-reminder.sock
+// 套接字所在的目录只有当前用户可以访问，不需要 token
+use crate::app_core::CoreExt;
+use crate::setting::{set_remind_later_impl, snooze_impl};
+use crate::slash_command::{load_view, TaskView};
+use crate::task_manager::{
+    add_task_impl, load_tags_impl, load_tasks_impl, update_task_impl, SaveResult, Task, TaskParams,
+};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs::{DirBuilder, Permissions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+pub const SOCKET_DIR: &str = "task-reminder";
+pub const SOCKET_FILE: &str = "task-reminder.sock";
+
+// 其他进程调用时等待响应的时间
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// JSON-RPC 2.0 错误码
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    jsonrpc: String,
+    id: Option<Value>, // 没有 id 时为通知，不返回结果
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct LoadTasksParams {
+    params: Option<TaskParams>,
+}
+
+#[derive(Deserialize)]
+struct TaskArgs {
+    task: Task,
+}
+
+#[derive(Deserialize)]
+struct RemindLaterParams {
+    hours: u64,
+}
+
+#[derive(Deserialize)]
+struct SnoozeParams {
+    minutes: u64,
+}
+
+#[derive(Deserialize)]
+struct ViewParams {
+    #[serde(default = "default_view")]
+    view: TaskView,
+}
+
+fn default_view() -> TaskView {
+    TaskView::Today
+}
+
+/// 套接字路径：$XDG_RUNTIME_DIR/task-reminder/ 下，
+/// 没有运行时目录时（例如 macOS）使用临时目录下按用户区分的 task-reminder-<uid>/
+pub fn socket_path() -> PathBuf {
+    let dir = match dirs::runtime_dir() {
+        Some(dir) => dir.join(SOCKET_DIR),
+        None => std::env::temp_dir().join(format!("{}-{}", SOCKET_DIR, current_uid())),
+    };
+    dir.join(SOCKET_FILE)
+}
+
+/// 在后台线程中监听套接字，每个连接一个线程
+pub fn start(app: &AppHandle) {
+    let path = socket_path();
+    // 先创建只有当前用户可以访问的目录，bind 之后、修改权限之前其他用户也无法连接
+    if let Some(dir) = path.parent() {
+        if let Err(e) = private_dir(dir) {
+            log::error!("create socket dir {:?} error: {}", dir, e);
+            return;
+        }
+    }
+    // 单实例运行，已存在的文件是上次退出时留下的
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::error!("remove stale socket {:?} error: {:?}", path, e);
+            return;
+        }
+    }
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!("bind socket {:?} error: {:?}", path, e);
+            return;
+        }
+    };
+    if let Err(e) = std::fs::set_permissions(&path, Permissions::from_mode(0o600)) {
+        log::error!("set socket permissions error: {:?}", e);
+        return;
+    }
+    log::info!("ipc listening on {:?}", path);
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    std::thread::spawn(move || serve(&app, stream));
+                }
+                Err(e) => log::warn!("accept ipc connection error: {:?}", e),
+            }
+        }
+    });
+}
+
+/// 退出时删除套接字文件
+pub fn stop() {
+    let path = socket_path();
+    if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::error!("remove socket {:?} error: {:?}", path, e);
+        }
+    }
+}
+
+/// 调用运行中应用的方法，用于命令行等其他进程，连接失败说明应用没有运行
+pub fn request(method: &str, params: Value) -> Result<Value, String> {
+    request_at(&socket_path(), method, params)
+}
+
+fn request_at(path: &Path, method: &str, params: Value) -> Result<Value, String> {
+    let mut stream = UnixStream::connect(path).map_err(|e| {
+        log::info!("connect {:?} error: {:?}", path, e);
+        "应用未运行，请先启动应用".to_string()
+    })?;
+    stream
+        .set_read_timeout(Some(REQUEST_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    writeln!(stream, "{}", request).map_err(|e| e.to_string())?;
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .map_err(|e| format!("等待应用响应失败: {}", e))?;
+    let response: Value = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+    match response.get("error") {
+        Some(error) => Err(error["message"].as_str().unwrap_or_default().to_string()),
+        None => Ok(response["result"].clone()),
+    }
+}
+
+fn current_uid() -> u32 {
+    unsafe { libc::getuid() }
+}
+
+// 创建只有当前用户可以访问的目录；临时目录所有用户都可以写入，
+// 已存在的目录可能是其他用户预先创建的，需要检查所有者
+fn private_dir(dir: &Path) -> Result<(), String> {
+    match DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e.to_string()),
+    }
+    let metadata = std::fs::symlink_metadata(dir).map_err(|e| e.to_string())?;
+    if !metadata.is_dir() || metadata.uid() != current_uid() {
+        return Err("不是当前用户的目录".to_string());
+    }
+    if metadata.mode() & 0o077 != 0 {
+        std::fs::set_permissions(dir, Permissions::from_mode(0o700)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn serve(app: &AppHandle, stream: UnixStream) {
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(e) => {
+            log::warn!("clone ipc stream error: {:?}", e);
+            return;
+        }
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = tauri::async_runtime::block_on(handle_line(app, &line)) else {
+            continue;
+        };
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
+
+async fn handle_line(app: &AppHandle, line: &str) -> Option<Value> {
+    let request: RpcRequest = match serde_json::from_str::<Value>(line) {
+        Ok(value) => match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => return Some(error_response(Value::Null, INVALID_REQUEST, e.to_string())),
+        },
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+    };
+    if request.jsonrpc != "2.0" {
+        let id = request.id.unwrap_or(Value::Null);
+        return Some(error_response(id, INVALID_REQUEST, "jsonrpc 必须为 2.0"));
+    }
+
+    log::info!("ipc call: {}", request.method);
+    let result = call(app, &request.method, request.params).await;
+    let id = request.id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e.code, e.message),
+    })
+}
+
+async fn call(app: &AppHandle, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "load_tasks" => {
+            let LoadTasksParams { params } = parse_params(params)?;
+            to_value(load_tasks_impl(&params, app.core()).await)
+        }
+        "add_task" => {
+            let TaskArgs { task } = parse_params(params)?;
+            let result = add_task_impl(&task, app.core()).await;
+            notify_changed(app, &result);
+            to_value(result)
+        }
+        "update_task" => {
+            let TaskArgs { task } = parse_params(params)?;
+            let result = update_task_impl(&task, app.core()).await;
+            notify_changed(app, &result);
+            to_value(result)
+        }
+        "set_remind_later" => {
+            let RemindLaterParams { hours } = parse_params(params)?;
+            set_remind_later_impl(hours, app.core());
+            Ok(Value::Null)
+        }
+        "snooze" => {
+            let SnoozeParams { minutes } = parse_params(params)?;
+            if minutes == 0 {
+                return Err(RpcError {
+                    code: INVALID_PARAMS,
+                    message: "时长必须大于 0".to_string(),
+                });
+            }
+            snooze_impl(minutes, app.core());
+            Ok(Value::Null)
+        }
+        "load_tags" => to_value(load_tags_impl(app.core()).await),
+        "load_view" => {
+            let ViewParams { view } = parse_params(params)?;
+            to_value(load_view(view, app.core()).await)
+        }
+        other => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: format!("未知的方法: {}", other),
+        }),
+    }
+}
+
+// 没有参数的方法也按对象解析，例如 load_tasks 可以不传 params
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError {
+        code: INVALID_PARAMS,
+        message: e.to_string(),
+    })
+}
+
+fn to_value<T: serde::Serialize>(result: Result<T, String>) -> Result<Value, RpcError> {
+    result
+        .and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string()))
+        .map_err(|message| RpcError {
+            code: SERVER_ERROR,
+            message,
+        })
+}
+
+// 任务由界面之外修改，通知主窗口刷新
+fn notify_changed(app: &AppHandle, result: &Result<SaveResult, String>) {
+    if matches!(result, Ok(result) if result.success) {
+        let _ = app.emit("tasks_changed", ());
+    }
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(path: &Path) -> u32 {
+        std::fs::metadata(path).unwrap().mode() & 0o777
+    }
+
+    #[test]
+    fn socket_is_in_a_per_user_dir() {
+        let path = socket_path();
+        assert!(path.ends_with(SOCKET_FILE));
+        let dir = path.parent().unwrap().file_name().unwrap();
+        let expected = match dirs::runtime_dir() {
+            Some(_) => SOCKET_DIR.to_string(),
+            None => format!("{}-{}", SOCKET_DIR, current_uid()),
+        };
+        assert_eq!(dir.to_string_lossy(), expected);
+    }
+
+    #[test]
+    fn request_reports_app_not_running() {
+        let dir = std::env::temp_dir().join(format!("task-reminder-rpc-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SOCKET_FILE);
+
+        let error = request_at(&path, "snooze", json!({ "minutes": 30 })).unwrap_err();
+        assert_eq!(error, "应用未运行，请先启动应用");
+
+        // 按行读取请求，第一次返回结果，第二次返回错误
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for (i, stream) in listener.incoming().take(2).enumerate() {
+                let stream = stream.unwrap();
+                let mut line = String::new();
+                BufReader::new(&stream).read_line(&mut line).unwrap();
+                requests.push(serde_json::from_str::<Value>(&line).unwrap());
+                let response = if i == 0 {
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": null })
+                } else {
+                    error_response(json!(1), INVALID_PARAMS, "时长必须大于 0")
+                };
+                writeln!(&stream, "{}", response).unwrap();
+            }
+            requests
+        });
+
+        assert_eq!(
+            request_at(&path, "snooze", json!({ "minutes": 30 })),
+            Ok(Value::Null)
+        );
+        assert_eq!(
+            request_at(&path, "snooze", json!({ "minutes": 0 })),
+            Err("时长必须大于 0".to_string())
+        );
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0]["method"], "snooze");
+        assert_eq!(requests[0]["params"]["minutes"], 30);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn private_dir_is_only_accessible_by_owner() {
+        let dir = std::env::temp_dir().join(format!("task-reminder-ipc-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        private_dir(&dir).unwrap();
+        assert_eq!(mode(&dir), 0o700);
+
+        // 已存在但其他用户可以访问时收紧权限
+        std::fs::set_permissions(&dir, Permissions::from_mode(0o755)).unwrap();
+        private_dir(&dir).unwrap();
+        assert_eq!(mode(&dir), 0o700);
+
+        // 同名的文件或链接不能当作目录
+        let file = dir.join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(private_dir(&file).is_err());
+        let link = dir.join("link");
+        std::os::unix::fs::symlink(&dir, &link).unwrap();
+        assert!(private_dir(&link).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
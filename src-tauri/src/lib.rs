@@ -1,14 +1,38 @@
 // #[cfg_attr(mobile, tauri::mobile_entry_point)]
 
+mod app_core;
+mod cli;
+mod data_dir;
+mod deep_link;
+mod escalation;
+mod export;
+mod hooks;
+mod http_api;
+mod idle;
+mod import;
+mod instance;
+#[cfg(unix)]
+mod ipc;
+#[cfg(test)]
+mod mock_notion;
 mod notion;
+mod reminder;
+mod reminder_log;
 mod setting;
+mod setting_store;
+mod shortcut;
+mod slash_command;
+mod startup;
 mod task_manager;
+mod task_parser;
+mod tray;
+mod webhook;
+mod widget;
 mod window_manager; // 声明模块
 use tauri_plugin_autostart::MacosLauncher;
 
-use tauri::menu::{MenuBuilder, MenuItemBuilder};
 use tauri::tray::{TrayIconBuilder, TrayIconEvent};
-use tauri::{Manager, WindowEvent};
+use tauri::{Emitter, Manager, WindowEvent};
 
 use std::env;
 use std::path::PathBuf;
@@ -36,10 +60,46 @@ fn open_settings_window(app: tauri::AppHandle) {
     }
 }
 
+// 初始化环境变量
+// 1. 开发环境：.env 在项目根目录
+// 2. 打包后：.env 在资源目录，命令行没有 AppHandle，由调用方传入
+fn load_env(resource_dir: Option<PathBuf>) {
+    let env_path = if cfg!(debug_assertions) {
+        // 开发环境：.env 在项目根目录
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent().unwrap()
+        .join(".env")
+    } else {
+        // 生产环境
+        let Some(resource_dir) = resource_dir else {
+            log::error!("Failed to get resource dir");
+            return;
+        };
+        resource_dir.join(".env")
+    };
+
+    log::info!("env_path: {:?}", env_path);
+    dotenv::from_path(env_path).ok();
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let context = tauri::generate_context!();
+
+    // 带子命令启动时作为命令行运行，不创建窗口
+    if let Some(cli) = cli::parse_args() {
+        std::process::exit(cli::run(cli, context));
+    }
+
     tauri::Builder::default()
+        // 单实例插件需要最先注册，再次启动时把参数交给当前程序处理
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            instance::on_second_instance(app, args);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_updater::Builder::new().build())
         // .plugin(tauri_plugin_window_state::Builder::new().build())
         .plugin(
@@ -57,57 +117,42 @@ pub fn run() {
         ))
         .setup(|app| {
 
-            // 初始化环境变量
-            // 1. 开发环境：.env 在项目根目录
-            // 2. 打包后：.env 在资源目录
-            let env_path = if cfg!(debug_assertions) {
-                // 开发环境：.env 在项目根目录
-                PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
-                .parent().unwrap()
-                .join(".env")
-            } else {
-                // 生产环境
-                let resource_dir = app
-                    .path()
-                    .resource_dir()
-                    .expect("Failed to get resource dir");
-                resource_dir.join(".env")
-            };
-
-            log::info!("env_path: {:?}", env_path);
-            dotenv::from_path(env_path).ok();
+            // 核心状态：配置目录、登录信息等，所有命令通过 app.core() 使用
+            let config_dir = app.path().app_config_dir()?;
+            let handle = app.handle().clone();
+            // 核心只发出事件，托盘和窗口在这里处理
+            app.manage(app_core::TaskReminderCore::new(config_dir).with_events(
+                move |event, payload| {
+                    match event {
+                        "task_saved" => tray::spawn_refresh_tray_menu(&handle),
+                        "reminder_snoozed" => {
+                            if let Some(window) = handle.get_webview_window("main") {
+                                let _ = window.hide();
+                            }
+                        }
+                        _ => {}
+                    }
+                    if let Err(e) = handle.emit(event, payload) {
+                        log::error!("emit {} error: {:?}", event, e);
+                    }
+                },
+            ));
+
+            load_env(app.path().resource_dir().ok());
 
             log::info!("setup");
 
-            // 初始化菜单
-            let toggle = MenuItemBuilder::new("Show/Hide").id("toggle").build(app)?;
-            let quit = MenuItemBuilder::new("Quit").id("quit").build(app)?;
-            let settings = MenuItemBuilder::new("Settings").id("settings").build(app)?;
-            let menu = MenuBuilder::new(app)
-                .item(&toggle)
-                .item(&settings)
-                .separator()
-                .item(&quit)
-                .build()
-                .unwrap();
+            // 初始化菜单，登录后会加载今日任务重建
+            let menu = tray::build_tray_menu(app.handle(), &[])?;
 
-            TrayIconBuilder::with_id("tray")
+            TrayIconBuilder::with_id(tray::TRAY_ID)
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&menu)
                 //.menu_on_left_click(true)
                 .show_menu_on_left_click(true)
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "quit" => app.exit(0),
-                    "toggle" => {
-                        let win = app.get_webview_window("main").unwrap();
-                        //window_manager::move_to_top_right(&win).unwrap();
-                        if win.is_visible().unwrap_or(false) {
-                            win.hide().unwrap();
-                        } else {
-                            win.show().unwrap();
-                            win.set_focus().unwrap();
-                        }
-                    }
+                    "toggle" => window_manager::toggle_main_window(app),
                     "settings" => {
                         open_settings_window(app.app_handle().clone());
                         // // 检查是否已存在 settings 窗口
@@ -130,21 +175,20 @@ pub fn run() {
                         //     .expect("failed to create settings window");
                         // }
                     }
-                    _ => {}
+                    id => tray::handle_tray_menu_event(app, id),
                 })
-                .on_tray_icon_event(|tray_handle, event: TrayIconEvent| match event {
-                    TrayIconEvent::DoubleClick { .. } => {
+                .on_tray_icon_event(|tray_handle, event: TrayIconEvent| {
+                    if let TrayIconEvent::DoubleClick { .. } = event {
                         if let Some(win) = tray_handle.app_handle().get_webview_window("main") {
-                            win.show().ok();
-                            win.set_focus().ok();
+                            window_manager::show_popup(tray_handle.app_handle(), &win);
                         }
                     }
-                    _ => {}
                 })
                 .build(app)
                 .unwrap();
 
-            // 初始化main窗口
+            // 初始化main窗口，按启动选项决定是否显示
+            let show_main = startup::on_launch(app.handle());
             tauri::WebviewWindowBuilder::new(
                 app,
                 "main", // 窗口唯一标识
@@ -152,28 +196,78 @@ pub fn run() {
                 )
                 .inner_size(600.0, 400.0)
                 .title("PUT FIRST THINGS FIRST")
-                .visible(true)
+                .visible(show_main)
                 .build()
                 .expect("failed to create main window");
             
             
+            // 注册全局快捷键，冲突留到设置窗口打开时提示；按设置启动本地 HTTP 接口
+            if let Ok(setting) = setting::load_setting_impl(app.handle()) {
+                let errors = shortcut::register_shortcuts(app.handle(), &setting.shortcuts);
+                shortcut::report_shortcut_conflicts(app.handle(), errors);
+                http_api::apply_http_api(app.handle(), &setting.http_api);
+            }
+
+            // 外部修改 setting.json 后立即生效
+            setting::watch_setting_file(app.handle());
+
+            // 恢复上次的小组件模式
+            widget::restore_window_mode(app.handle());
+
             // 启动定时弹窗
             window_manager::start_periodic_popup(app.handle().clone(), "main");
 
             notion::init_auth_info(app.handle());
 
+            // 处理启动参数，例如 --add "任务"、--show
+            instance::handle_launch_args(app.handle());
+
+            // 处理 task-reminder:// 链接
+            deep_link::init(app.handle());
+
+            // 本地脚本和状态栏通过 Unix 套接字调用命令
+            #[cfg(unix)]
+            ipc::start(app.handle());
+
+            // 加载今日任务到托盘菜单，并定时更新托盘提示
+            tray::spawn_refresh_tray_menu(app.handle());
+            tray::start_tray_status_updater(app.handle());
+
+            // 运行中跨过零点时触发 day_started 钩子
+            startup::start_day_watcher(app.handle());
+
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } => {
                 window.hide().unwrap();
                 api.prevent_close();
             }
+            // 记录主窗口位置，供"上次位置"使用
+            WindowEvent::Moved(position) if window.label() == "main" => {
+                window_manager::save_last_position(window, position);
+            }
+            // 快速添加窗口失去焦点时隐藏
+            WindowEvent::Focused(false) if window.label() == shortcut::QUICK_ADD_LABEL => {
+                let _ = window.hide();
+            }
+            // 记录小组件的位置和大小
+            WindowEvent::Moved(_) | WindowEvent::Resized(_)
+                if window.label() == widget::WIDGET_LABEL =>
+            {
+                widget::save_widget_geometry(window);
+            }
+            _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             setting::save_setting,
             setting::load_setting,
             setting::set_remind_later,
+            setting_store::take_setting_warning,
+            startup::take_plan_day,
+            deep_link::take_open_task,
+            reminder_log::load_reminder_stats,
+            webhook::load_webhook_log,
             notion::load_auth_info,
             notion::save_auth_info,
             notion::clear_auth_info,
@@ -187,9 +281,31 @@ pub fn run() {
             
             task_manager::load_tags,
             task_manager::update_tags,
+            export::export_tasks,
+            import::import_tasks,
+            task_parser::parse_task,
+            slash_command::run_slash_command,
 
             open_settings_window,
+            shortcut::hide_quick_add_window,
+            shortcut::take_quick_add_text,
+            shortcut::take_shortcut_conflicts,
+            widget::set_window_mode,
+            widget::toggle_window_mode,
+            widget::load_window_mode,
         ])
-        .run(tauri::generate_context!())
-        .expect("failed to run app");
+        .build(context)
+        .expect("failed to build app")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                on_exit();
+            }
+        });
+}
+
+// 退出前的清理
+fn on_exit() {
+    // 删除本地脚本接口的套接字文件
+    #[cfg(unix)]
+    ipc::stop();
 }
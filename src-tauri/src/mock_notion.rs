@@ -0,0 +1,285 @@
+// src-tauri/src/mock_notion.rs
+// 测试用的 Notion 接口：在本地端口模拟页面的创建、修改、读取和数据库查询，
+// 不需要网络和 Tauri 运行时，配合 TaskReminderCore::with_notion_api_url 使用
+//   let notion = MockNotion::start();
+//   let (core, events) = notion.core();
+//   add_task_impl(&task, &core) 之后用 notion.task(id) 和 events 检查结果
+// 查询时忽略过滤条件，返回所有页面，按 page_size 分页
+use crate::app_core::TaskReminderCore;
+use crate::notion::{AuthInfo, User};
+use crate::task_manager::Task;
+
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tiny_http::{Header, Method, Response, Server};
+
+pub type Events = Arc<Mutex<Vec<(String, Value)>>>;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+#[derive(Default)]
+struct State {
+    pages: Vec<Value>,
+    requests: Vec<String>, // 例如 "PATCH /v1/pages/<id>"
+    page_size: usize,
+}
+
+pub struct MockNotion {
+    pub url: String,
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    dirs: Mutex<Vec<PathBuf>>,
+}
+
+impl MockNotion {
+    pub fn start() -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("bind mock notion"));
+        let url = format!("http://{}", server.server_addr());
+        let state = Arc::new(Mutex::new(State {
+            page_size: 100,
+            ..Default::default()
+        }));
+
+        let (thread_server, thread_state) = (server.clone(), state.clone());
+        std::thread::spawn(move || {
+            for mut request in thread_server.incoming_requests() {
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+                let body = serde_json::from_str(&body).unwrap_or(Value::Null);
+                let (status, response) =
+                    handle(&thread_state, request.method(), request.url(), &body);
+                let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+                let response = Response::from_string(response.to_string())
+                    .with_status_code(status)
+                    .with_header(header);
+                let _ = request.respond(response);
+            }
+        });
+
+        MockNotion {
+            url,
+            server,
+            state,
+            dirs: Mutex::new(vec![]),
+        }
+    }
+
+    /// 已登录的核心状态，配置目录为新的临时目录，返回的 events 记录发出的事件
+    pub fn core(&self) -> (TaskReminderCore, Events) {
+        let (core, events) = self.logged_out_core();
+        core.save_auth_info(&test_auth()).expect("save auth");
+        (core, events)
+    }
+
+    pub fn logged_out_core(&self) -> (TaskReminderCore, Events) {
+        let dir = temp_dir();
+        self.dirs.lock().unwrap().push(dir.clone());
+        let events: Events = Arc::default();
+        let recorder = events.clone();
+        let core = TaskReminderCore::new(dir)
+            .with_notion_api_url(&self.url)
+            .with_events(move |event, payload| {
+                recorder.lock().unwrap().push((event.to_string(), payload));
+            });
+        (core, events)
+    }
+
+    /// 直接写入一个页面，返回页面 id
+    pub fn add_page(&self, task: &Task) -> String {
+        let mut state = self.state.lock().unwrap();
+        let page = new_page(&properties(task));
+        let id = page["id"].as_str().unwrap().to_string();
+        state.pages.push(page);
+        id
+    }
+
+    pub fn task(&self, id: &str) -> Option<Task> {
+        let state = self.state.lock().unwrap();
+        let page = state.pages.iter().find(|page| page["id"] == id)?;
+        serde_json::from_value(json!({
+            "id": page["id"],
+            "text": page["properties"]["task"]["title"][0]["plain_text"],
+            "percent": page["properties"]["percent"]["number"],
+            "status": page["properties"]["status"]["status"]["name"],
+            "time": page["properties"]["time"]["date"],
+            "tags": page["properties"]["tags"]["multi_select"]
+                .as_array()
+                .map(|tags| tags.iter().map(|tag| tag["name"].clone()).collect::<Vec<_>>()),
+        }))
+        .ok()
+    }
+
+    /// 每次查询最多返回的页面数，默认与 Notion 相同为 100
+    pub fn set_page_size(&self, page_size: usize) {
+        self.state.lock().unwrap().page_size = page_size.max(1);
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockNotion {
+    fn drop(&mut self) {
+        self.server.unblock();
+        for dir in self.dirs.lock().unwrap().iter() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// 事件名列表，例如 ["task_saved", "tasks_changed"]
+pub fn event_names(events: &Events) -> Vec<String> {
+    events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+pub fn test_task(text: &str, status: &str, start: &str) -> Task {
+    serde_json::from_value(json!({
+        "id": "",
+        "text": text,
+        "percent": 0,
+        "status": status,
+        "time": { "start": start, "end": null, "time_zone": null },
+        "tags": [],
+    }))
+    .unwrap()
+}
+
+fn handle(state: &Mutex<State>, method: &Method, url: &str, body: &Value) -> (u16, Value) {
+    let mut state = state.lock().unwrap();
+    state.requests.push(format!("{} {}", method, url));
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        (Method::Post, ["v1", "pages"]) => {
+            let page = new_page(&body["properties"]);
+            state.pages.push(page.clone());
+            (200, page)
+        }
+        (Method::Get, ["v1", "pages", id]) => match find_page(&mut state, id) {
+            Some(page) => (200, page.clone()),
+            None => not_found(),
+        },
+        (Method::Patch, ["v1", "pages", id]) => match find_page(&mut state, id) {
+            Some(page) => {
+                if let (Some(properties), Some(changes)) = (
+                    page["properties"].as_object_mut(),
+                    with_plain_text(&body["properties"]).as_object(),
+                ) {
+                    properties.extend(changes.clone());
+                }
+                (200, page.clone())
+            }
+            None => not_found(),
+        },
+        (Method::Post, ["v1", "databases", _, "query"]) => {
+            let start = body["start_cursor"]
+                .as_str()
+                .and_then(|cursor| cursor.parse::<usize>().ok())
+                .unwrap_or(0);
+            let page_size = body["page_size"]
+                .as_u64()
+                .map(|size| size as usize)
+                .unwrap_or(100)
+                .min(state.page_size);
+            let end = (start + page_size).min(state.pages.len());
+            let has_more = end < state.pages.len();
+            (
+                200,
+                json!({
+                    "object": "list",
+                    "results": state.pages[start.min(end)..end],
+                    "has_more": has_more,
+                    "next_cursor": if has_more { Some(end.to_string()) } else { None },
+                }),
+            )
+        }
+        _ => (
+            400,
+            json!({ "object": "error", "code": "invalid_request_url", "message": url }),
+        ),
+    }
+}
+
+fn find_page<'a>(state: &'a mut State, id: &str) -> Option<&'a mut Value> {
+    state.pages.iter_mut().find(|page| page["id"] == id)
+}
+
+fn not_found() -> (u16, Value) {
+    (
+        404,
+        json!({ "object": "error", "code": "object_not_found", "message": "not found" }),
+    )
+}
+
+fn new_page(properties: &Value) -> Value {
+    let id = format!("{:032x}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
+    json!({
+        "object": "page",
+        "id": id,
+        "archived": false,
+        "properties": with_plain_text(properties),
+    })
+}
+
+// 请求中的标题为 text.content，返回的页面中为 plain_text
+fn with_plain_text(properties: &Value) -> Value {
+    let mut properties = properties.clone();
+    if let Some(title) = properties["task"]["title"].as_array_mut() {
+        for item in title {
+            item["plain_text"] = item["text"]["content"].clone();
+        }
+    }
+    properties
+}
+
+fn properties(task: &Task) -> Value {
+    json!({
+        "task": { "title": [{ "text": { "content": task.text } }] },
+        "percent": { "number": task.percent },
+        "status": { "status": { "name": task.status } },
+        "time": { "date": task.time },
+        "tags": {
+            "multi_select": task.tags.iter().flatten().map(|tag| json!({ "name": tag })).collect::<Vec<_>>(),
+        },
+    })
+}
+
+fn temp_dir() -> PathBuf {
+    static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+    std::env::temp_dir().join(format!(
+        "task-reminder-test-{}-{}",
+        std::process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+    ))
+}
+
+pub fn test_auth() -> AuthInfo {
+    AuthInfo {
+        access_token: "test-token".to_string(),
+        bot_id: "bot".to_string(),
+        duplicated_template_id: "database".to_string(),
+        user: User {
+            id: "user".to_string(),
+            object: "user".to_string(),
+            name: None,
+            r#type: None,
+            avatar_url: None,
+            person: None,
+        },
+        refresh_token: None,
+        request_id: "request".to_string(),
+        token_type: "bearer".to_string(),
+        workspace_icon: None,
+        workspace_id: "workspace".to_string(),
+        workspace_name: "test".to_string(),
+    }
+}
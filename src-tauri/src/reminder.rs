@@ -0,0 +1,303 @@
+// src-tauri/src/reminder.rs
+// 提醒渠道：定时器到点后，按配置依次通过各个渠道发出提醒
+use crate::setting::{load_setting_impl, Setting};
+use crate::task_manager::Task;
+use crate::widget::{active_widget, remind_widget};
+use crate::window_manager::show_popup;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use tauri_plugin_notification::NotificationExt;
+
+// 渠道配置，保存在 Setting.channels 中
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChannelConfig {
+    // 弹出并聚焦任务窗口
+    Window {
+        enabled: bool,
+    },
+    // 系统通知
+    Notification {
+        enabled: bool,
+    },
+    // 播放提示音，file 为空时使用系统默认声音
+    Sound {
+        enabled: bool,
+        #[serde(default)]
+        file: Option<String>,
+    },
+    // 向外部地址 POST 提醒内容
+    Webhook {
+        enabled: bool,
+        url: String,
+    },
+    // 执行本地命令，提醒内容以 JSON 写入 stdin
+    Command {
+        enabled: bool,
+        command: String,
+    },
+}
+
+// 一次提醒的内容
+#[derive(Serialize)]
+pub struct Reminder {
+    pub window_label: String,
+    pub fired_at: String,
+    pub tasks: Vec<Task>, // 未完成的任务，按 percent 降序
+    pub level: u32,       // 升级等级，见 escalation
+}
+
+impl Reminder {
+    pub fn new(window_label: &str, tasks: Vec<Task>, level: u32) -> Self {
+        Reminder {
+            window_label: window_label.to_string(),
+            fired_at: Local::now().to_rfc3339(),
+            tasks,
+            level,
+        }
+    }
+
+    // 提醒文案，例如 "还有 3 个任务未完成：写周报"
+    pub fn summary(&self) -> String {
+        match self.tasks.first() {
+            Some(task) => format!("还有 {} 个任务未完成：{}", self.tasks.len(), task.text),
+            None => "今天的任务都完成了".to_string(),
+        }
+    }
+}
+
+pub trait ReminderChannel: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn remind(&self, app: &AppHandle, reminder: &Reminder) -> Result<(), String>;
+}
+
+// 原有的弹窗行为
+pub struct WindowChannel;
+
+impl ReminderChannel for WindowChannel {
+    fn name(&self) -> &'static str {
+        "window"
+    }
+
+    fn remind(&self, app: &AppHandle, reminder: &Reminder) -> Result<(), String> {
+        if let Some(widget) = active_widget(app) {
+            return remind_widget(&widget);
+        }
+        let Some(window) = app.get_webview_window(&reminder.window_label) else {
+            return Err(format!("window {} not found", reminder.window_label));
+        };
+        show_popup(app, &window);
+        Ok(())
+    }
+}
+
+pub struct NotificationChannel;
+
+impl ReminderChannel for NotificationChannel {
+    fn name(&self) -> &'static str {
+        "notification"
+    }
+
+    fn remind(&self, app: &AppHandle, reminder: &Reminder) -> Result<(), String> {
+        app.notification()
+            .builder()
+            .title("PUT FIRST THINGS FIRST")
+            .body(reminder.summary())
+            .show()
+            .map_err(|e| e.to_string())
+    }
+}
+
+pub struct SoundChannel {
+    pub file: Option<String>,
+}
+
+impl ReminderChannel for SoundChannel {
+    fn name(&self) -> &'static str {
+        "sound"
+    }
+
+    fn remind(&self, _app: &AppHandle, _reminder: &Reminder) -> Result<(), String> {
+        let mut command = if cfg!(target_os = "macos") {
+            let mut c = Command::new("afplay");
+            c.arg(
+                self.file
+                    .as_deref()
+                    .unwrap_or("/System/Library/Sounds/Glass.aiff"),
+            );
+            c
+        } else if cfg!(target_os = "windows") {
+            let script = match &self.file {
+                Some(file) => format!(
+                    "(New-Object Media.SoundPlayer {}).PlaySync()",
+                    powershell_quote(file)
+                ),
+                None => "[System.Media.SystemSounds]::Asterisk.Play()".to_string(),
+            };
+            let mut c = Command::new("powershell");
+            c.args(["-NoProfile", "-Command", &script]);
+            c
+        } else {
+            let mut c = Command::new("paplay");
+            c.arg(
+                self.file
+                    .as_deref()
+                    .unwrap_or("/usr/share/sounds/freedesktop/stereo/complete.oga"),
+            );
+            c
+        };
+
+        let mut child = command.spawn().map_err(|e| e.to_string())?;
+        // 等待子进程退出，避免残留僵尸进程
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+pub struct WebhookChannel {
+    pub url: String,
+}
+
+impl ReminderChannel for WebhookChannel {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn remind(&self, _app: &AppHandle, reminder: &Reminder) -> Result<(), String> {
+        let url = self.url.clone();
+        let body = json!({
+            "event": "reminder",
+            "fired_at": reminder.fired_at,
+            "text": reminder.summary(),
+            "tasks": reminder.tasks,
+        });
+
+        tauri::async_runtime::spawn(async move {
+            let res = reqwest::Client::new()
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .body(body.to_string())
+                .send()
+                .await;
+            match res {
+                Ok(res) => log::info!("webhook {} status: {}", url, res.status()),
+                Err(e) => log::error!("webhook {} error: {:?}", url, e),
+            }
+        });
+        Ok(())
+    }
+}
+
+pub struct CommandChannel {
+    pub command: String,
+}
+
+impl ReminderChannel for CommandChannel {
+    fn name(&self) -> &'static str {
+        "command"
+    }
+
+    fn remind(&self, _app: &AppHandle, reminder: &Reminder) -> Result<(), String> {
+        let mut command = shell_command(&self.command);
+        let payload = serde_json::to_string(reminder).map_err(|e| e.to_string())?;
+        let mut child = command
+            .env("TASK_REMINDER_TEXT", reminder.summary())
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        std::thread::spawn(move || {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(payload.as_bytes());
+            }
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+// PowerShell 单引号字符串，其中的单引号（包括弯引号）需要重复一次
+fn powershell_quote(text: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in text.chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// 通过系统 shell 执行用户配置的命令，支持管道和重定向
+pub fn shell_command(command: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", command]);
+        c
+    }
+}
+
+// 根据配置创建已启用的渠道，升级提醒开启时低等级的弹窗以系统通知代替
+pub fn build_channels(setting: &Setting, level: u32) -> Vec<Box<dyn ReminderChannel>> {
+    let notify_only = setting.escalation.use_notification(level);
+    setting
+        .channels
+        .iter()
+        .filter_map(|config| -> Option<Box<dyn ReminderChannel>> {
+            match config {
+                ChannelConfig::Window { enabled: true } if notify_only => {
+                    Some(Box::new(NotificationChannel))
+                }
+                ChannelConfig::Window { enabled: true } => Some(Box::new(WindowChannel)),
+                ChannelConfig::Notification { enabled: true } => {
+                    Some(Box::new(NotificationChannel))
+                }
+                ChannelConfig::Sound {
+                    enabled: true,
+                    file,
+                } => Some(Box::new(SoundChannel { file: file.clone() })),
+                ChannelConfig::Webhook { enabled: true, url } => {
+                    Some(Box::new(WebhookChannel { url: url.clone() }))
+                }
+                ChannelConfig::Command {
+                    enabled: true,
+                    command,
+                } => Some(Box::new(CommandChannel {
+                    command: command.clone(),
+                })),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// 通过所有已启用的渠道发出提醒，单个渠道失败不影响其他渠道
+pub fn dispatch(app: &AppHandle, reminder: &Reminder) {
+    let setting = match load_setting_impl(app) {
+        Ok(setting) => setting,
+        Err(e) => {
+            log::error!("dispatch load_setting_impl error: {}", e);
+            return;
+        }
+    };
+
+    for channel in build_channels(&setting, reminder.level) {
+        if let Err(e) = channel.remind(app, reminder) {
+            log::error!("reminder channel {} error: {}", channel.name(), e);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+// src-tauri/src/reminder_log.rs
+// 记录每次提醒的结果，用于统计提醒是否有效
+use crate::app_core::{CoreExt, TaskReminderCore};
+
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// 日志只保留最近的天数
+const KEEP_DAYS: i64 = 90;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Dismissed, // 关闭了弹窗
+    Snoozed,   // 稍后提醒
+    Completed, // 提醒后 N 分钟内完成了任务
+    Ignored,   // 没有响应
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReminderRecord {
+    pub fired_at: String,
+    pub outcome: Option<Outcome>,
+    pub responded_at: Option<String>,
+    pub snooze_minutes: Option<u64>,
+}
+
+impl ReminderRecord {
+    // 从提醒到响应经过的秒数
+    fn response_secs(&self) -> Option<i64> {
+        let fired = DateTime::parse_from_rfc3339(&self.fired_at).ok()?;
+        let responded = DateTime::parse_from_rfc3339(self.responded_at.as_ref()?).ok()?;
+        Some((responded - fired).num_seconds())
+    }
+
+    // 从提醒到现在经过的秒数
+    fn response_secs_from_now(&self) -> Option<i64> {
+        let fired = DateTime::parse_from_rfc3339(&self.fired_at).ok()?;
+        Some((Local::now().fixed_offset() - fired).num_seconds())
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct DailyStats {
+    pub date: String,
+    pub fired: u32,
+    pub dismissed: u32,
+    pub snoozed: u32,
+    pub completed: u32,
+    pub ignored: u32,
+    pub avg_response_secs: Option<f64>, // 平均响应时间（不含忽略）
+    pub avg_snooze_minutes: Option<f64>,
+    pub snooze_rate: f64, // 稍后提醒次数 / 提醒次数
+}
+
+#[tauri::command]
+pub fn load_reminder_stats(
+    days: Option<u32>,
+    app: tauri::AppHandle,
+) -> Result<Vec<DailyStats>, String> {
+    load_reminder_stats_impl(days.unwrap_or(7), app.core())
+}
+
+fn log_path(core: &TaskReminderCore) -> Result<std::path::PathBuf, String> {
+    core.data_file("reminder_log.json")
+}
+
+fn load_records(core: &TaskReminderCore) -> Result<Vec<ReminderRecord>, String> {
+    let file_path = log_path(core)?;
+    if !file_path.exists() {
+        return Ok(vec![]);
+    }
+    let json = std::fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| format!("解析 reminder_log.json 失败: {}", e))
+}
+
+fn save_records(records: &[ReminderRecord], core: &TaskReminderCore) -> Result<(), String> {
+    let file_path = log_path(core)?;
+    let json = serde_json::to_string_pretty(records).map_err(|e| e.to_string())?;
+    std::fs::write(&file_path, json).map_err(|e| e.to_string())
+}
+
+// 定时器触发提醒时调用，上一次仍未响应的提醒记为忽略
+pub fn record_fired(fired_at: &str, core: &TaskReminderCore) {
+    let previous = core.pending_reminder().replace(fired_at.to_string());
+
+    let result = load_records(core).and_then(|mut records| {
+        if let Some(previous) = previous {
+            if let Some(record) = records.iter_mut().find(|r| r.fired_at == previous) {
+                if record.outcome.is_none() {
+                    record.outcome = Some(Outcome::Ignored);
+                }
+            }
+        }
+
+        let cutoff = Local::now() - Duration::days(KEEP_DAYS);
+        records.retain(|r| {
+            DateTime::parse_from_rfc3339(&r.fired_at)
+                .map(|t| t > cutoff)
+                .unwrap_or(false)
+        });
+
+        records.push(ReminderRecord {
+            fired_at: fired_at.to_string(),
+            outcome: None,
+            responded_at: None,
+            snooze_minutes: None,
+        });
+        save_records(&records, core)
+    });
+
+    if let Err(e) = result {
+        log::error!("record_fired error: {}", e);
+    }
+}
+
+// 记录当前待响应提醒的结果
+pub fn record_outcome(outcome: Outcome, snooze_minutes: Option<u64>, core: &TaskReminderCore) {
+    let Some(fired_at) = core.pending_reminder().take() else {
+        return;
+    };
+
+    let result = load_records(core).and_then(|mut records| {
+        let Some(record) = records.iter_mut().find(|r| r.fired_at == fired_at) else {
+            return Ok(());
+        };
+        let elapsed = record.response_secs_from_now().unwrap_or_default();
+
+        // 提醒后很久才关闭弹窗，视为没有响应
+        let complete_within = core.load_setting().map(|s| s.complete_within).unwrap_or(10);
+        let too_late = elapsed > complete_within as i64 * 60;
+        record.outcome = if outcome == Outcome::Dismissed && too_late {
+            Some(Outcome::Ignored)
+        } else {
+            Some(outcome)
+        };
+        record.responded_at = Some(Local::now().to_rfc3339());
+        record.snooze_minutes = snooze_minutes;
+        save_records(&records, core)
+    });
+
+    if let Err(e) = result {
+        log::error!("record_outcome error: {}", e);
+    }
+}
+
+// 任务完成时调用：最近一次提醒在 N 分钟内且未被稍后提醒，记为完成
+pub fn record_task_completed(core: &TaskReminderCore) {
+    let complete_within = core.load_setting().map(|s| s.complete_within).unwrap_or(10);
+
+    let result = load_records(core).and_then(|mut records| {
+        let Some(record) = records.last_mut() else {
+            return Ok(());
+        };
+        if !matches!(record.outcome, None | Some(Outcome::Dismissed)) {
+            return Ok(());
+        }
+        let elapsed = record.response_secs_from_now().unwrap_or(i64::MAX);
+        if elapsed > complete_within as i64 * 60 {
+            return Ok(());
+        }
+
+        record.outcome = Some(Outcome::Completed);
+        record.responded_at = Some(Local::now().to_rfc3339());
+
+        let mut pending = core.pending_reminder();
+        if pending.as_deref() == Some(record.fired_at.as_str()) {
+            *pending = None;
+        }
+        drop(pending);
+
+        save_records(&records, core)
+    });
+
+    if let Err(e) = result {
+        log::error!("record_task_completed error: {}", e);
+    }
+}
+
+pub fn load_reminder_stats_impl(
+    days: u32,
+    core: &TaskReminderCore,
+) -> Result<Vec<DailyStats>, String> {
+    let records = load_records(core)?;
+    let first_day = Local::now().date_naive() - Duration::days(days.saturating_sub(1) as i64);
+
+    // 按本地日期分组
+    let mut by_day: BTreeMap<String, Vec<&ReminderRecord>> = BTreeMap::new();
+    for record in &records {
+        let Ok(fired) = DateTime::parse_from_rfc3339(&record.fired_at) else {
+            continue;
+        };
+        let date = fired.with_timezone(&Local).date_naive();
+        if date < first_day {
+            continue;
+        }
+        by_day.entry(date.to_string()).or_default().push(record);
+    }
+
+    let stats = by_day
+        .into_iter()
+        .map(|(date, records)| {
+            let mut stats = DailyStats {
+                date,
+                fired: records.len() as u32,
+                ..Default::default()
+            };
+
+            let mut response_secs = vec![];
+            let mut snooze_minutes = vec![];
+            for record in &records {
+                match record.outcome {
+                    Some(Outcome::Dismissed) => stats.dismissed += 1,
+                    Some(Outcome::Snoozed) => stats.snoozed += 1,
+                    Some(Outcome::Completed) => stats.completed += 1,
+                    Some(Outcome::Ignored) => stats.ignored += 1,
+                    None => {}
+                }
+                if record.outcome != Some(Outcome::Ignored) {
+                    if let Some(secs) = record.response_secs() {
+                        response_secs.push(secs as f64);
+                    }
+                }
+                if let Some(minutes) = record.snooze_minutes {
+                    snooze_minutes.push(minutes as f64);
+                }
+            }
+
+            stats.avg_response_secs = average(&response_secs);
+            stats.avg_snooze_minutes = average(&snooze_minutes);
+            stats.snooze_rate = stats.snoozed as f64 / stats.fired as f64;
+            stats
+        })
+        .collect();
+
+    Ok(stats)
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_notion::MockNotion;
+    use chrono::TimeZone;
+
+    fn record(
+        fired_at: DateTime<Local>,
+        outcome: Option<Outcome>,
+        response_secs: Option<i64>,
+        snooze_minutes: Option<u64>,
+    ) -> ReminderRecord {
+        ReminderRecord {
+            fired_at: fired_at.to_rfc3339(),
+            outcome,
+            responded_at: response_secs
+                .map(|secs| (fired_at + Duration::seconds(secs)).to_rfc3339()),
+            snooze_minutes,
+        }
+    }
+
+    // 距今若干天的中午，避免跨越午夜
+    fn noon(days_ago: i64) -> DateTime<Local> {
+        let day = Local::now().date_naive() - Duration::days(days_ago);
+        Local
+            .from_local_datetime(&day.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn record_fired_ignores_previous_and_prunes_old_records() {
+        let notion = MockNotion::start();
+        let (core, _) = notion.core();
+        let old = record(
+            Local::now() - Duration::days(KEEP_DAYS + 1),
+            None,
+            None,
+            None,
+        );
+        let previous = record(Local::now() - Duration::hours(1), None, None, None);
+        save_records(&[old, previous.clone()], &core).unwrap();
+        *core.pending_reminder() = Some(previous.fired_at.clone());
+
+        let fired_at = Local::now().to_rfc3339();
+        record_fired(&fired_at, &core);
+
+        let records = load_records(&core).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].fired_at, previous.fired_at);
+        assert_eq!(records[0].outcome, Some(Outcome::Ignored));
+        assert_eq!(records[1].fired_at, fired_at);
+        assert_eq!(records[1].outcome, None);
+        assert_eq!(core.pending_reminder().as_deref(), Some(fired_at.as_str()));
+    }
+
+    #[test]
+    fn stats_aggregate_records_by_day() {
+        let notion = MockNotion::start();
+        let (core, _) = notion.core();
+        let records = [
+            record(noon(8), Some(Outcome::Dismissed), Some(10), None),
+            record(noon(1), Some(Outcome::Completed), Some(120), None),
+            record(noon(0), Some(Outcome::Dismissed), Some(30), None),
+            record(noon(0), Some(Outcome::Snoozed), Some(60), Some(10)),
+            record(noon(0), Some(Outcome::Ignored), Some(900), None),
+            record(noon(0), None, None, None),
+        ];
+        save_records(&records, &core).unwrap();
+
+        let stats = load_reminder_stats_impl(7, &core).unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let yesterday = &stats[0];
+        assert_eq!(yesterday.date, noon(1).date_naive().to_string());
+        assert_eq!(yesterday.fired, 1);
+        assert_eq!(yesterday.completed, 1);
+        assert_eq!(yesterday.avg_response_secs, Some(120.0));
+        assert_eq!(yesterday.avg_snooze_minutes, None);
+        assert_eq!(yesterday.snooze_rate, 0.0);
+
+        let today = &stats[1];
+        assert_eq!(today.date, noon(0).date_naive().to_string());
+        assert_eq!(today.fired, 4);
+        assert_eq!(today.dismissed, 1);
+        assert_eq!(today.snoozed, 1);
+        assert_eq!(today.ignored, 1);
+        assert_eq!(today.completed, 0);
+        // 忽略的提醒不计入响应时间
+        assert_eq!(today.avg_response_secs, Some(45.0));
+        assert_eq!(today.avg_snooze_minutes, Some(10.0));
+        assert_eq!(today.snooze_rate, 0.25);
+
+        assert_eq!(load_reminder_stats_impl(9, &core).unwrap().len(), 3);
+    }
+}
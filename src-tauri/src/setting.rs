@@ -1,25 +1,136 @@
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
 
+use notify::{RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
-use tauri::Manager;
+use tauri::Emitter;
 
-pub static GLOBAL_REMIND_TIME: Lazy<Mutex<u64>> = Lazy::new(|| Mutex::new(0));
+use crate::app_core::{CoreExt, TaskReminderCore};
+use crate::data_dir::relocate;
+use crate::escalation::{self, EscalationSetting};
+use crate::hooks::ShellHook;
+use crate::http_api::{apply_http_api, HttpApiSetting};
+use crate::idle::IdleSetting;
+use crate::reminder::ChannelConfig;
+use crate::reminder_log::{record_outcome, Outcome};
+use crate::setting_store::{parse_setting, SETTING_FILE, SETTING_VERSION};
+use crate::shortcut::{
+    register_shortcuts, report_shortcut_conflicts, ShortcutError, ShortcutSetting,
+};
+use crate::startup::{apply_autostart, StartupSetting};
+use crate::webhook::{self, WebhookConfig, WebhookEvent};
+use crate::window_manager::WindowAnchor;
 
-#[derive(Serialize, Deserialize)]
+// 每次新配置生效时加一，提醒循环据此重新读取配置
+static SETTING_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// 最近一次写入或生效的 setting.json 内容，用于忽略自己写文件触发的变更
+static LAST_SETTING_JSON: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(String::new()));
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Setting {
+    #[serde(default)]
+    pub version: u32, // 配置格式版本，见 setting_store
+
     pub remind_time: u64, // 定期提醒时间（分钟）
-    pub path: String,     // 配置路径
+    pub path: String,     // 数据目录，见 data_dir
+
+    #[serde(default = "default_channels")]
+    pub channels: Vec<ChannelConfig>, // 提醒渠道，按顺序依次触发
+
+    #[serde(default = "default_complete_within")]
+    pub complete_within: u64, // 提醒后多少分钟内完成任务算作提醒有效
+
+    #[serde(default)]
+    pub escalation: EscalationSetting, // 重要任务被忽略时升级提醒
+
+    #[serde(default)]
+    pub idle: IdleSetting, // 空闲和休眠时暂停提醒
+
+    #[serde(default)]
+    pub anchor: WindowAnchor, // 弹窗显示的位置
+
+    #[serde(default)]
+    pub shortcuts: ShortcutSetting, // 全局快捷键
+
+    #[serde(default)]
+    pub startup: StartupSetting, // 开机启动和启动时的窗口
+
+    #[serde(default)]
+    pub http_api: HttpApiSetting, // 本地 HTTP 接口，默认关闭
+
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>, // 任务事件的外发 webhook
+
+    #[serde(default)]
+    pub hooks: Vec<ShellHook>, // 事件发生时执行的本地命令
+}
+
+// 首次启动或配置无法恢复时使用的默认配置
+pub fn default_setting(config_dir: &Path) -> Setting {
+    Setting {
+        version: SETTING_VERSION,
+        remind_time: 60,
+        path: config_dir.to_string_lossy().to_string(),
+        channels: default_channels(),
+        complete_within: default_complete_within(),
+        escalation: EscalationSetting::default(),
+        idle: IdleSetting::default(),
+        anchor: WindowAnchor::default(),
+        shortcuts: ShortcutSetting::default(),
+        startup: StartupSetting::default(),
+        http_api: HttpApiSetting::default(),
+        webhooks: vec![],
+        hooks: vec![],
+    }
+}
+
+fn default_channels() -> Vec<ChannelConfig> {
+    vec![ChannelConfig::Window { enabled: true }]
+}
+
+fn default_complete_within() -> u64 {
+    10
 }
 
 #[derive(Serialize)]
 pub struct SaveResult {
     success: bool,
+    shortcut_errors: Vec<ShortcutError>, // 注册失败的快捷键，由设置窗口提示
 }
 
 #[tauri::command]
-pub fn save_setting(setting: Setting, app: tauri::AppHandle) -> Result<SaveResult, String> {
-    save_setting_impl(&setting, &app)
+pub fn save_setting(mut setting: Setting, app: tauri::AppHandle) -> Result<SaveResult, String> {
+    setting.version = SETTING_VERSION;
+    // 内容没有变化时不写文件，避免各窗口互相同步时反复保存
+    let json = serde_json::to_string_pretty(&setting).map_err(|e| e.to_string())?;
+    if *LAST_SETTING_JSON.lock().unwrap() == json {
+        return Ok(SaveResult {
+            success: true,
+            shortcut_errors: vec![],
+        });
+    }
+
+    // 数据目录改变时先把数据文件复制到新目录，配置保存失败则回滚
+    let relocation = relocate(&setting.path, app.core())?;
+    match save_setting_impl(&setting, &app) {
+        Ok(mut result) => {
+            if let Some(relocation) = relocation {
+                relocation.commit(app.core());
+            }
+            result.shortcut_errors = apply_setting(&setting, &app);
+            Ok(result)
+        }
+        Err(e) => {
+            if let Some(relocation) = relocation {
+                relocation.rollback();
+            }
+            Err(e)
+        }
+    }
 }
 
 #[tauri::command]
@@ -29,61 +140,123 @@ pub fn load_setting(app: tauri::AppHandle) -> Result<Setting, String> {
 
 // 保存配置
 pub fn save_setting_impl(setting: &Setting, app: &tauri::AppHandle) -> Result<SaveResult, String> {
-    let config_dir = app.path().app_config_dir().unwrap();
+    let json = app.core().save_setting(setting)?;
+    *LAST_SETTING_JSON.lock().unwrap() = json;
+    Ok(SaveResult {
+        success: true,
+        shortcut_errors: vec![],
+    })
+}
 
-    let file_path = config_dir.join("setting.json");
+/// 使新配置立即生效：重新注册快捷键、重启本地接口、通知提醒循环和所有窗口，
+/// 返回注册失败的快捷键
+pub fn apply_setting(setting: &Setting, app: &tauri::AppHandle) -> Vec<ShortcutError> {
+    SETTING_GENERATION.fetch_add(1, Ordering::SeqCst);
+    // 例如直接编辑 setting.json 修改了数据目录，不迁移文件
+    match app.core().resolve_data_dir(&setting.path) {
+        Ok(dir) => app.core().set_data_dir(dir),
+        Err(e) => log::error!("set_data_dir error: {}", e),
+    }
+    let shortcut_errors = register_shortcuts(app, &setting.shortcuts);
+    apply_autostart(app, &setting.startup);
+    apply_http_api(app, &setting.http_api);
+    if let Err(e) = app.emit("setting_changed", setting) {
+        log::error!("emit setting_changed error: {:?}", e);
+    }
+    shortcut_errors
+}
 
-    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
-    let json = serde_json::to_string_pretty(&setting).map_err(|e| e.to_string())?;
-    std::fs::write(&file_path, json).map_err(|e| e.to_string())?;
-    Ok(SaveResult { success: true })
+pub fn setting_generation() -> u64 {
+    SETTING_GENERATION.load(Ordering::SeqCst)
 }
 
-// 加载配置
-pub fn load_setting_impl(app: &tauri::AppHandle) -> Result<Setting, String> {
-    let config_dir = app.path().app_config_dir().unwrap();
-
-    let file_path = config_dir.join("setting.json");
-
-    // 文件不存在时，
-    if !file_path.exists() {
-        return Ok(Setting {
-            remind_time: 60,
-            path: app
-                .path()
-                .app_config_dir()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_string(),
-        });
+/// 监听 setting.json，外部修改后无需重启即可生效
+pub fn watch_setting_file(app: &tauri::AppHandle) {
+    let config_dir = app.core().config_dir().to_path_buf();
+    if let Err(e) = std::fs::create_dir_all(&config_dir) {
+        log::error!("watch_setting_file create_dir_all error: {:?}", e);
+        return;
     }
 
-    let json = std::fs::read_to_string(file_path).unwrap();
-    let setting: Setting = serde_json::from_str(&json).unwrap();
-    Ok(setting)
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel();
+        // 监听目录而不是文件，编辑器保存时常常是替换文件
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                log::error!("create setting watcher error: {:?}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&config_dir, RecursiveMode::NonRecursive) {
+            log::error!("watch {:?} error: {:?}", config_dir, e);
+            return;
+        }
+
+        let file_path = config_dir.join(SETTING_FILE);
+        while let Ok(event) = rx.recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            let touched = event.paths.iter().any(|path| path.ends_with(SETTING_FILE));
+            if !touched {
+                continue;
+            }
+            // 一次保存会触发多个事件，等写入完成后只处理一次
+            std::thread::sleep(Duration::from_millis(300));
+            while rx.try_recv().is_ok() {}
+            reload_setting_file(&app, &file_path);
+        }
+    });
+}
+
+fn reload_setting_file(app: &tauri::AppHandle, file_path: &Path) {
+    let Ok(json) = std::fs::read_to_string(file_path) else {
+        return;
+    };
+    if *LAST_SETTING_JSON.lock().unwrap() == json {
+        return;
+    }
+    // 可能还在编辑中，格式错误时只提示，不从备份恢复
+    match parse_setting(&json, app.core().config_dir()) {
+        Ok((setting, _)) => {
+            log::info!("setting.json changed, apply new setting");
+            *LAST_SETTING_JSON.lock().unwrap() = json;
+            let errors = apply_setting(&setting, app);
+            report_shortcut_conflicts(app, errors);
+        }
+        Err(e) => app
+            .core()
+            .warn(format!("setting.json 格式错误，修改未生效：{}", e)),
+    }
+}
+
+// 加载配置
+pub fn load_setting_impl(app: &tauri::AppHandle) -> Result<Setting, String> {
+    app.core().load_setting()
 }
 
 #[tauri::command]
 pub fn set_remind_later(hours: u64, app: tauri::AppHandle) -> Result<(), String> {
-    set_remind_later_impl(hours, &app);
+    set_remind_later_impl(hours, app.core());
     Ok(())
 }
 
-pub fn set_remind_later_impl(hours: u64, app: &tauri::AppHandle) {
+pub fn set_remind_later_impl(hours: u64, core: &TaskReminderCore) {
     println!("set_remind_later: {}", hours);
-    *GLOBAL_REMIND_TIME.lock().unwrap() = hours;
-    let Some(window) = app.get_webview_window("main") else {
-        return;
-    };
-    window.hide().unwrap();
+    snooze_impl(hours * 60, core);
+}
+
+// 推迟提醒，单位分钟，界面收到 reminder_snoozed 后隐藏弹窗
+pub fn snooze_impl(minutes: u64, core: &TaskReminderCore) {
+    core.set_remind_later(minutes);
+    record_outcome(Outcome::Snoozed, Some(minutes), core);
+    webhook::fire(core, WebhookEvent::ReminderSnoozed, None, Some(minutes));
+    escalation::reset(core);
+    core.emit("reminder_snoozed", minutes);
 }
 
 pub fn get_remind_later_impl(app: &tauri::AppHandle) -> u64 {
-    if *GLOBAL_REMIND_TIME.lock().unwrap() == 0 {
-        let setting = load_setting_impl(app).unwrap();
-        return setting.remind_time;
-    } else {
-        return *GLOBAL_REMIND_TIME.lock().unwrap() * 60;
-    }
+    app.core().remind_later()
 }
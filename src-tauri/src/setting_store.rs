@@ -0,0 +1,239 @@
+// src-tauri/src/setting_store.rs
+// setting.json 的读写：版本迁移、缺失字段补默认值、原子写入、损坏时从备份恢复
+use crate::app_core::{CoreExt, TaskReminderCore};
+use crate::setting::{default_setting, Setting};
+
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use tauri::AppHandle;
+
+// 当前的配置版本，修改字段含义时加一并在 MIGRATIONS 中添加迁移步骤
+pub const SETTING_VERSION: u32 = 1;
+
+pub const SETTING_FILE: &str = "setting.json";
+const BACKUP_FILE: &str = "setting.json.bak";
+const CORRUPT_FILE: &str = "setting.json.corrupt";
+
+type Migration = fn(&mut Map<String, Value>);
+
+// MIGRATIONS[n] 把版本 n 的配置升级到 n + 1
+const MIGRATIONS: [Migration; SETTING_VERSION as usize] = [migrate_v0_to_v1];
+
+// v0 为没有版本号的配置，字段与 v1 相同，只需写入版本号
+fn migrate_v0_to_v1(_setting: &mut Map<String, Value>) {}
+
+/// 解析配置内容，依次执行迁移并补齐缺失的字段，返回配置和是否发生了迁移
+pub fn parse_setting(json: &str, config_dir: &Path) -> Result<(Setting, bool), String> {
+    let mut value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let Some(object) = value.as_object_mut() else {
+        return Err("setting.json is not an object".to_string());
+    };
+
+    let version = object.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > SETTING_VERSION {
+        log::warn!(
+            "setting.json version {} is newer than {}",
+            version,
+            SETTING_VERSION
+        );
+    }
+    for migrate in MIGRATIONS.iter().skip(version as usize) {
+        migrate(object);
+    }
+
+    let defaults = serde_json::to_value(default_setting(config_dir)).map_err(|e| e.to_string())?;
+    if let Value::Object(defaults) = defaults {
+        for (key, value) in defaults {
+            object.entry(key).or_insert(value);
+        }
+    }
+    object.insert("version".to_string(), json!(version.max(SETTING_VERSION)));
+
+    let setting = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    Ok((setting, version < SETTING_VERSION))
+}
+
+/// 读取配置，旧版本迁移后写回，文件损坏时从备份或默认配置恢复
+pub fn read_setting(core: &TaskReminderCore) -> Result<Setting, String> {
+    let config_dir = core.config_dir();
+    let result = fs::read_to_string(config_dir.join(SETTING_FILE))
+        .map_err(|e| e.to_string())
+        .and_then(|json| parse_setting(&json, config_dir));
+
+    match result {
+        Ok((setting, migrated)) => {
+            if migrated {
+                log::info!("migrate setting.json to version {}", SETTING_VERSION);
+                if let Err(e) = write_setting(&setting, config_dir) {
+                    log::error!("write migrated setting error: {}", e);
+                }
+            }
+            Ok(setting)
+        }
+        Err(e) => recover_setting(core, &e),
+    }
+}
+
+fn recover_setting(core: &TaskReminderCore, error: &str) -> Result<Setting, String> {
+    log::warn!("setting.json unreadable: {}", error);
+    let config_dir = core.config_dir();
+
+    // 保留损坏的文件便于排查，同时避免恢复时覆盖备份
+    let _ = fs::rename(config_dir.join(SETTING_FILE), config_dir.join(CORRUPT_FILE));
+
+    let backup = fs::read_to_string(config_dir.join(BACKUP_FILE))
+        .map_err(|e| e.to_string())
+        .and_then(|json| parse_setting(&json, config_dir));
+    let (setting, message) = match backup {
+        Ok((setting, _)) => (setting, format!("配置文件损坏，已从备份恢复：{}", error)),
+        Err(_) => (
+            default_setting(config_dir),
+            format!("配置文件损坏，已恢复默认配置：{}", error),
+        ),
+    };
+
+    write_setting(&setting, config_dir)?;
+    core.warn(message);
+    Ok(setting)
+}
+
+/// 原子写入配置，覆盖前把当前可用的配置保存为备份，返回写入的内容
+pub fn write_setting(setting: &Setting, config_dir: &Path) -> Result<String, String> {
+    fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(setting).map_err(|e| e.to_string())?;
+
+    let file_path = config_dir.join(SETTING_FILE);
+    if let Ok(current) = fs::read_to_string(&file_path) {
+        if current != json && serde_json::from_str::<Value>(&current).is_ok() {
+            write_atomic(&config_dir.join(BACKUP_FILE), &current)?;
+        }
+    }
+    write_atomic(&file_path, &json)?;
+    Ok(json)
+}
+
+// 先写临时文件再重命名，写入中途崩溃也不会留下半个文件
+fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    let tmp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+    file.write_all(contents.as_bytes())
+        .map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+// 启动时窗口还未监听事件，由前端主动获取最近一次的配置警告
+#[tauri::command]
+pub fn take_setting_warning(app: AppHandle) -> Result<Option<String>, String> {
+    Ok(app.core().take_warning())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "task-reminder-setting-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fills_missing_fields_and_stamps_version() {
+        let dir = PathBuf::from("/config");
+        let (setting, migrated) =
+            parse_setting(r#"{"remind_time": 0, "path": "/data"}"#, &dir).unwrap();
+        assert!(migrated);
+        assert_eq!(setting.version, SETTING_VERSION);
+        // 迁移不修改已有的值
+        assert_eq!(setting.remind_time, 0);
+        assert_eq!(setting.path, "/data");
+        assert_eq!(setting.complete_within, 10);
+        assert_eq!(setting.channels.len(), 1);
+        assert!(!setting.http_api.enabled);
+
+        let json = format!(r#"{{"version": {}, "remind_time": 30}}"#, SETTING_VERSION);
+        let (setting, migrated) = parse_setting(&json, &dir).unwrap();
+        assert!(!migrated);
+        assert_eq!(setting.path, "/config");
+    }
+
+    #[test]
+    fn keeps_newer_version() {
+        let json = format!(
+            r#"{{"version": {}, "remind_time": 30, "path": "", "future": true}}"#,
+            SETTING_VERSION + 1
+        );
+        let (setting, migrated) = parse_setting(&json, Path::new("/config")).unwrap();
+        assert!(!migrated);
+        assert_eq!(setting.version, SETTING_VERSION + 1);
+        assert_eq!(setting.remind_time, 30);
+    }
+
+    #[test]
+    fn recovers_corrupt_setting_from_backup() {
+        let dir = temp_dir("corrupt");
+        let mut backup = default_setting(&dir);
+        backup.remind_time = 45;
+        let backup_json = serde_json::to_string_pretty(&backup).unwrap();
+        fs::write(dir.join(BACKUP_FILE), &backup_json).unwrap();
+        fs::write(dir.join(SETTING_FILE), "{\"remind_time\": ").unwrap();
+        let core = TaskReminderCore::new(dir.clone());
+
+        let setting = read_setting(&core).unwrap();
+        assert_eq!(setting.remind_time, 45);
+        assert_eq!(
+            fs::read_to_string(dir.join(CORRUPT_FILE)).unwrap(),
+            "{\"remind_time\": "
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join(SETTING_FILE)).unwrap(),
+            backup_json
+        );
+        assert!(core
+            .take_warning()
+            .unwrap()
+            .starts_with("配置文件损坏，已从备份恢复"));
+
+        // 备份也无法使用时恢复默认配置
+        fs::write(dir.join(SETTING_FILE), "[]").unwrap();
+        fs::write(dir.join(BACKUP_FILE), "").unwrap();
+        let setting = read_setting(&core).unwrap();
+        assert_eq!(setting.remind_time, 60);
+        assert_eq!(fs::read_to_string(dir.join(CORRUPT_FILE)).unwrap(), "[]");
+        assert!(core
+            .take_warning()
+            .unwrap()
+            .starts_with("配置文件损坏，已恢复默认配置"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn write_keeps_backup_and_leaves_no_temp_files() {
+        let dir = temp_dir("write");
+        let mut setting = default_setting(&dir);
+        let first = write_setting(&setting, &dir).unwrap();
+        setting.remind_time = 30;
+        let second = write_setting(&setting, &dir).unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join(SETTING_FILE)).unwrap(), second);
+        assert_eq!(fs::read_to_string(dir.join(BACKUP_FILE)).unwrap(), first);
+        let mut files: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, [SETTING_FILE, BACKUP_FILE]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,144 @@
+// src-tauri/src/shortcut.rs
+// 全局快捷键：切换主窗口、打开快速添加窗口
+use crate::window_manager::toggle_main_window;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+pub const QUICK_ADD_LABEL: &str = "quick_add";
+
+// 待填入快速添加窗口的内容，新建的窗口还未监听事件，由前端主动获取
+static PENDING_TEXT: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+// 设置窗口未打开时注册失败的快捷键，打开后由前端获取并提示
+static PENDING_CONFLICTS: Lazy<Mutex<Vec<ShortcutError>>> = Lazy::new(|| Mutex::new(vec![]));
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ShortcutSetting {
+    pub toggle: String,    // 显示/隐藏主窗口，为空时不注册
+    pub quick_add: String, // 打开快速添加窗口，为空时不注册
+}
+
+impl Default for ShortcutSetting {
+    fn default() -> Self {
+        ShortcutSetting {
+            toggle: "CommandOrControl+Shift+T".to_string(),
+            quick_add: "CommandOrControl+Shift+A".to_string(),
+        }
+    }
+}
+
+// 注册失败的快捷键，显示在设置窗口
+#[derive(Serialize, Clone)]
+pub struct ShortcutError {
+    pub name: String,
+    pub shortcut: String,
+    pub error: String,
+}
+
+/// 重新注册全部快捷键，返回注册失败（格式错误或与其他程序冲突）的快捷键
+pub fn register_shortcuts(app: &AppHandle, setting: &ShortcutSetting) -> Vec<ShortcutError> {
+    let global_shortcut = app.global_shortcut();
+    if let Err(e) = global_shortcut.unregister_all() {
+        log::error!("unregister_all error: {:?}", e);
+    }
+
+    // 快捷键名称、按键、按下时的操作
+    type Action<'a> = (&'a str, &'a str, fn(&AppHandle));
+    let actions: [Action; 2] = [
+        ("toggle", &setting.toggle, toggle_main_window),
+        ("quick_add", &setting.quick_add, open_quick_add_window),
+    ];
+
+    let mut errors = vec![];
+    for (name, shortcut, action) in actions {
+        if shortcut.trim().is_empty() {
+            continue;
+        }
+        let result = global_shortcut.on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                action(app);
+            }
+        });
+        if let Err(e) = result {
+            log::error!("register shortcut {} {} error: {:?}", name, shortcut, e);
+            errors.push(ShortcutError {
+                name: name.to_string(),
+                shortcut: shortcut.to_string(),
+                error: e.to_string(),
+            });
+        }
+    }
+    errors
+}
+
+/// 提示注册失败的快捷键：设置窗口已打开时直接发送，否则保留到窗口打开后获取
+pub fn report_shortcut_conflicts(app: &AppHandle, errors: Vec<ShortcutError>) {
+    if errors.is_empty() {
+        return;
+    }
+    if app.get_webview_window("settings").is_some() {
+        if let Err(e) = app.emit_to("settings", "shortcut_conflict", errors) {
+            log::error!("emit shortcut_conflict error: {:?}", e);
+        }
+        return;
+    }
+    *PENDING_CONFLICTS.lock().unwrap() = errors;
+}
+
+#[tauri::command]
+pub fn take_shortcut_conflicts() -> Result<Vec<ShortcutError>, String> {
+    Ok(std::mem::take(&mut *PENDING_CONFLICTS.lock().unwrap()))
+}
+
+/// 打开快速添加窗口
+pub fn open_quick_add_window(app: &AppHandle) {
+    if let Some(win) = app.get_webview_window(QUICK_ADD_LABEL) {
+        let _ = win.center();
+        let _ = win.show();
+        let _ = win.set_focus();
+        return;
+    }
+
+    log::info!("create quick add window");
+    let result = tauri::WebviewWindowBuilder::new(
+        app,
+        QUICK_ADD_LABEL,
+        tauri::WebviewUrl::App("#/quick-add".into()),
+    )
+    .inner_size(420.0, 64.0)
+    .title("快速添加")
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .center()
+    .focused(true)
+    .build();
+    if let Err(e) = result {
+        log::error!("create quick add window error: {:?}", e);
+    }
+}
+
+/// 打开快速添加窗口并填入内容，由用户确认后保存，用于链接等外部入口
+pub fn prefill_quick_add_window(app: &AppHandle, text: String) {
+    *PENDING_TEXT.lock().unwrap() = Some(text);
+    open_quick_add_window(app);
+    let _ = app.emit_to(QUICK_ADD_LABEL, "quick_add_prefill", ());
+}
+
+#[tauri::command]
+pub fn take_quick_add_text() -> Result<Option<String>, String> {
+    Ok(PENDING_TEXT.lock().unwrap().take())
+}
+
+#[tauri::command]
+pub fn hide_quick_add_window(app: AppHandle) -> Result<(), String> {
+    if let Some(win) = app.get_webview_window(QUICK_ADD_LABEL) {
+        win.hide().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
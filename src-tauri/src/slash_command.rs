@@ -0,0 +1,318 @@
+// src-tauri/src/slash_command.rs
+// 斜杠命令：在任务输入框、托盘和命令行中共用
+//   /done 2          完成第 2 个任务
+//   /snooze 1h       稍后提醒
+//   /move 3 tomorrow 把第 3 个任务移到明天
+//   /tag 1 +work -home
+//   /view overdue    查看任务：today / overdue / week / done
+// 任务序号从 1 开始，对应今日未完成任务的顺序（与主窗口 "进行中" 列表一致）
+use crate::app_core::{CoreExt, TaskReminderCore};
+use crate::setting::snooze_impl;
+use crate::task_manager::{load_tasks_impl, update_task_impl, Task, TaskParams};
+use crate::task_parser::{parse_duration, parse_task_text};
+
+use chrono::{Datelike, Duration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskView {
+    Today,   // 今日未完成
+    Overdue, // 今天之前未完成
+    Week,    // 本周未完成
+    Done,    // 今日已完成
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SlashCommand {
+    Done {
+        index: usize,
+    },
+    Snooze {
+        minutes: u64,
+    },
+    Move {
+        index: usize,
+        when: String,
+    },
+    Tag {
+        index: usize,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    View {
+        view: TaskView,
+    },
+    Help,
+}
+
+#[derive(Serialize, Default)]
+pub struct CommandResult {
+    pub success: bool,
+    pub action: String, // 执行的命令，例如 "done"
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<Task>, // 被修改的任务
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<Vec<Task>>, // /view 查询到的任务
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>, // 命令行查询到的标签
+}
+
+impl CommandResult {
+    pub fn ok(action: &str, message: String) -> Self {
+        CommandResult {
+            success: true,
+            action: action.to_string(),
+            message,
+            ..Default::default()
+        }
+    }
+
+    pub fn failed(action: &str, message: String) -> Self {
+        CommandResult {
+            success: false,
+            action: action.to_string(),
+            message,
+            ..Default::default()
+        }
+    }
+}
+
+const HELP: &str = "/done <序号>  完成任务
+/snooze <时长>  稍后提醒，例如 1h、30m
+/move <序号> <时间>  修改任务时间，例如 tomorrow、下周一 3pm
+/tag <序号> +标签 -标签  添加或删除标签
+/view today|overdue|week|done  查看任务";
+
+#[tauri::command]
+pub async fn run_slash_command(input: String, app: AppHandle) -> Result<CommandResult, String> {
+    run_slash_command_impl(&input, app.core()).await
+}
+
+pub async fn run_slash_command_impl(
+    input: &str,
+    core: &TaskReminderCore,
+) -> Result<CommandResult, String> {
+    log::info!("run_slash_command: {}", input);
+    let command = match parse_slash_command(input) {
+        Ok(command) => command,
+        Err(e) => return Ok(CommandResult::failed("parse", e)),
+    };
+    execute(command, core).await
+}
+
+/// 解析斜杠命令，格式错误时返回提示信息
+pub fn parse_slash_command(input: &str) -> Result<SlashCommand, String> {
+    let input = input.trim();
+    let Some(body) = input.strip_prefix('/') else {
+        return Err("命令需要以 / 开头".to_string());
+    };
+    let (name, args) = match body.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (body, ""),
+    };
+
+    match name.to_lowercase().as_str() {
+        "done" | "d" => Ok(SlashCommand::Done {
+            index: parse_index(args)?,
+        }),
+        "snooze" | "s" => {
+            // 不带单位时按小时，与托盘菜单一致
+            let minutes = match args.parse::<u64>() {
+                Ok(hours) => hours * 60,
+                Err(_) => parse_duration(args)
+                    .filter(|m| *m > 0)
+                    .ok_or_else(|| format!("无法识别的时长: {}", args))?
+                    as u64,
+            };
+            Ok(SlashCommand::Snooze { minutes })
+        }
+        "move" | "mv" => {
+            let (index, when) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+            if when.trim().is_empty() {
+                return Err("用法: /move <序号> <时间>".to_string());
+            }
+            Ok(SlashCommand::Move {
+                index: parse_index(index)?,
+                when: when.trim().to_string(),
+            })
+        }
+        "tag" | "t" => {
+            let mut words = args.split_whitespace();
+            let index = parse_index(words.next().unwrap_or_default())?;
+            let mut add = vec![];
+            let mut remove = vec![];
+            for word in words {
+                match word.strip_prefix('-') {
+                    Some(tag) if !tag.is_empty() => remove.push(tag.to_string()),
+                    Some(_) => {}
+                    None => {
+                        let tag = word.trim_start_matches(['+', '#']);
+                        if !tag.is_empty() {
+                            add.push(tag.to_string());
+                        }
+                    }
+                }
+            }
+            if add.is_empty() && remove.is_empty() {
+                return Err("用法: /tag <序号> +标签 -标签".to_string());
+            }
+            Ok(SlashCommand::Tag { index, add, remove })
+        }
+        "view" | "v" => {
+            let view = match args.to_lowercase().as_str() {
+                "" | "today" | "今天" => TaskView::Today,
+                "overdue" | "逾期" => TaskView::Overdue,
+                "week" | "本周" => TaskView::Week,
+                "done" | "完成" => TaskView::Done,
+                other => return Err(format!("未知的视图: {}", other)),
+            };
+            Ok(SlashCommand::View { view })
+        }
+        "help" | "h" | "?" => Ok(SlashCommand::Help),
+        other => Err(format!("未知命令: /{}，输入 /help 查看帮助", other)),
+    }
+}
+
+fn parse_index(text: &str) -> Result<usize, String> {
+    match text.trim().parse::<usize>() {
+        Ok(index) if index > 0 => Ok(index),
+        _ => Err(format!("无效的任务序号: {}", text)),
+    }
+}
+
+pub async fn execute(
+    command: SlashCommand,
+    core: &TaskReminderCore,
+) -> Result<CommandResult, String> {
+    match command {
+        SlashCommand::Done { index } => {
+            let Some(mut task) = find_task(index, core).await? else {
+                return Ok(not_found("done", index));
+            };
+            task.mark_done();
+            save_task("done", task, core, "已完成").await
+        }
+        SlashCommand::Snooze { minutes } => {
+            snooze_impl(minutes, core);
+            Ok(CommandResult::ok(
+                "snooze",
+                format!("{} 分钟后再提醒", minutes),
+            ))
+        }
+        SlashCommand::Move { index, when } => {
+            let Some(mut task) = find_task(index, core).await? else {
+                return Ok(not_found("move", index));
+            };
+            let parsed = parse_task_text(&when, Local::now());
+            if !parsed.has_date || !parsed.task.text.is_empty() {
+                return Ok(CommandResult::failed(
+                    "move",
+                    format!("无法识别的时间: {}", when),
+                ));
+            }
+            task.time = parsed.task.time;
+            save_task("move", task, core, "已移动").await
+        }
+        SlashCommand::Tag { index, add, remove } => {
+            let Some(mut task) = find_task(index, core).await? else {
+                return Ok(not_found("tag", index));
+            };
+            let mut tags = task.tags.take().unwrap_or_default();
+            tags.retain(|tag| !remove.contains(tag));
+            for tag in add {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+            task.tags = Some(tags);
+            save_task("tag", task, core, "已更新标签").await
+        }
+        SlashCommand::View { view } => {
+            let tasks = load_view(view, core).await?;
+            let mut result = CommandResult::ok("view", format!("共 {} 个任务", tasks.len()));
+            result.tasks = Some(tasks);
+            Ok(result)
+        }
+        SlashCommand::Help => Ok(CommandResult::ok("help", HELP.to_string())),
+    }
+}
+
+fn not_found(action: &str, index: usize) -> CommandResult {
+    CommandResult::failed(action, format!("没有第 {} 个任务", index))
+}
+
+// 按序号查找今日未完成的任务
+async fn find_task(index: usize, core: &TaskReminderCore) -> Result<Option<Task>, String> {
+    let tasks = load_view(TaskView::Today, core).await?;
+    Ok(tasks.into_iter().nth(index - 1))
+}
+
+async fn save_task(
+    action: &str,
+    task: Task,
+    core: &TaskReminderCore,
+    done_message: &str,
+) -> Result<CommandResult, String> {
+    let result = update_task_impl(&task, core).await?;
+    if !result.success {
+        return Ok(CommandResult::failed(
+            action,
+            result.error.unwrap_or_else(|| "保存失败".to_string()),
+        ));
+    }
+    core.emit("tasks_changed", ());
+
+    let mut result = CommandResult::ok(action, format!("{}: {}", done_message, task.text));
+    result.task = Some(task);
+    Ok(result)
+}
+
+pub async fn load_view(view: TaskView, core: &TaskReminderCore) -> Result<Vec<Task>, String> {
+    let today = Local::now().date_naive();
+    let day_start = |days: i64| {
+        let date = today + Duration::days(days);
+        Local
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|time| time.to_rfc3339())
+    };
+    let day_end = |days: i64| {
+        let date = today + Duration::days(days);
+        Local
+            .from_local_datetime(&date.and_hms_opt(23, 59, 59).unwrap())
+            .latest()
+            .map(|time| time.to_rfc3339())
+    };
+
+    let params = match view {
+        TaskView::Today => TaskParams::today("1"),
+        TaskView::Done => TaskParams::today("2"),
+        TaskView::Overdue => TaskParams {
+            start: None,
+            end: day_end(-1),
+            status: Some("1".to_string()),
+        },
+        TaskView::Week => {
+            let to_monday = today.weekday().num_days_from_monday() as i64;
+            TaskParams {
+                start: day_start(-to_monday),
+                end: day_end(6 - to_monday),
+                status: Some("1".to_string()),
+            }
+        }
+    };
+
+    let result = load_tasks_impl(&Some(params), core).await?;
+    if !result.success {
+        return Err(result
+            .status
+            .unwrap_or_else(|| "load tasks failed".to_string()));
+    }
+    Ok(result.tasks.map(|list| list.tasks).unwrap_or_default())
+}
@@ -0,0 +1,142 @@
+// src-tauri/src/startup.rs
+// 启动选项：开机启动、启动时隐藏到托盘、每天第一次启动时显示计划视图
+use crate::app_core::CoreExt;
+use crate::hooks::{self, HookEvent};
+use crate::setting::{load_setting_impl, save_setting_impl};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+use tauri_plugin_autostart::ManagerExt;
+use tokio::time::{sleep, Duration};
+
+// 本次启动是否需要显示计划视图，由主窗口加载后获取
+static PLAN_PENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StartupSetting {
+    pub launch_at_login: bool,      // 开机启动
+    pub start_hidden: bool,         // 启动时不显示主窗口，只显示托盘图标
+    pub plan_on_first_launch: bool, // 每天第一次启动时显示今日任务，即使设置了隐藏启动
+}
+
+impl Default for StartupSetting {
+    fn default() -> Self {
+        StartupSetting {
+            launch_at_login: false,
+            start_hidden: false,
+            plan_on_first_launch: true,
+        }
+    }
+}
+
+// 上次启动的日期，保存在 startup.json
+#[derive(Serialize, Deserialize, Default)]
+struct LaunchState {
+    last_launch: Option<String>,
+}
+
+/// 启动时调用：同步开机启动状态，返回主窗口是否显示
+pub fn on_launch(app: &AppHandle) -> bool {
+    let first_today = mark_launch_today(app);
+    if first_today {
+        fire_day_started(app);
+    }
+    let Ok(mut setting) = load_setting_impl(app) else {
+        return true;
+    };
+
+    // 用户可能在系统设置中修改过登录项，以系统的实际状态为准
+    match app.autolaunch().is_enabled() {
+        Ok(enabled) if enabled != setting.startup.launch_at_login => {
+            log::info!("sync launch_at_login from system: {}", enabled);
+            setting.startup.launch_at_login = enabled;
+            if let Err(e) = save_setting_impl(&setting, app) {
+                log::error!("save launch_at_login error: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("autolaunch is_enabled error: {}", e),
+    }
+
+    if first_today && setting.startup.plan_on_first_launch {
+        PLAN_PENDING.store(true, Ordering::SeqCst);
+        return true;
+    }
+    !setting.startup.start_hidden
+}
+
+/// 配置修改后开启或关闭开机启动
+pub fn apply_autostart(app: &AppHandle, startup: &StartupSetting) {
+    let autolaunch = app.autolaunch();
+    if autolaunch.is_enabled().ok() == Some(startup.launch_at_login) {
+        return;
+    }
+    let result = if startup.launch_at_login {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    if let Err(e) = result {
+        log::error!(
+            "set launch_at_login {} error: {}",
+            startup.launch_at_login,
+            e
+        );
+    }
+}
+
+/// 程序跨过零点继续运行时，也触发新的一天开始的钩子
+pub fn start_day_watcher(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(60)).await;
+            if mark_launch_today(&app) {
+                fire_day_started(&app);
+            }
+        }
+    });
+}
+
+fn fire_day_started(app: &AppHandle) {
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    hooks::fire(
+        app.core(),
+        HookEvent::DayStarted,
+        None,
+        json!({ "date": date }),
+    );
+}
+
+#[tauri::command]
+pub fn take_plan_day() -> Result<bool, String> {
+    Ok(PLAN_PENDING.swap(false, Ordering::SeqCst))
+}
+
+// 记录今天已启动过，返回是否为今天第一次启动
+fn mark_launch_today(app: &AppHandle) -> bool {
+    let config_dir = app.core().config_dir();
+    let file_path = config_dir.join("startup.json");
+    let today = Local::now().format("%Y-%m-%d").to_string();
+
+    let state: LaunchState = std::fs::read_to_string(&file_path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    if state.last_launch.as_deref() == Some(today.as_str()) {
+        return false;
+    }
+
+    let state = LaunchState {
+        last_launch: Some(today),
+    };
+    let result = std::fs::create_dir_all(config_dir)
+        .and_then(|_| std::fs::write(&file_path, serde_json::to_string(&state).unwrap()));
+    if let Err(e) = result {
+        log::error!("save startup.json error: {:?}", e);
+    }
+    true
+}
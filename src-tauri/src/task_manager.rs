@@ -1,21 +1,26 @@
-use crate::setting::load_setting_impl;
-use chrono::{Local, NaiveTime, TimeZone};
+use crate::app_core::{CoreExt, TaskReminderCore};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
 use serde_json::json;
 
-use crate::notion::get_auth_info_from_global;
+use crate::reminder_log::record_task_completed;
+use crate::webhook::{self, WebhookEvent};
+use crate::hooks::{self, HookEvent};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use tauri_plugin_http::reqwest;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Time {
     pub start: String,
     pub end: Option<String>,
     pub time_zone: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+// notion 中文和英文模板中任务的状态
+pub const TASK_STATUSES: [&str; 6] = ["未开始", "进行中", "完成", "Not started", "In progress", "Done"];
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Task {
     pub id: String,
     pub text: String,
@@ -25,6 +30,34 @@ pub struct Task {
     pub tags: Option<Vec<String>>,
 }
 
+impl Task {
+    // notion 中文和英文模板的完成状态
+    pub fn is_done(&self) -> bool {
+        self.status == "完成" || self.status == "Done"
+    }
+
+    pub fn is_not_started(&self) -> bool {
+        self.status == "未开始" || self.status == "Not started"
+    }
+
+    // 标记为完成，状态名与任务原有状态的语言保持一致
+    pub fn mark_done(&mut self) {
+        let english = self.status.is_ascii() && !self.status.is_empty();
+        self.status = if english { "Done" } else { "完成" }.to_string();
+        if self.time.end.is_none() {
+            self.time.end = Some(Local::now().to_rfc3339());
+        }
+    }
+
+    // 开始日期（本地时区），全天任务的 start 只有日期，例如 2026-10-19
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        DateTime::parse_from_rfc3339(&self.time.start)
+            .map(|time| time.with_timezone(&Local).date_naive())
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(self.time.start.get(..10)?, "%Y-%m-%d").ok())
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct TaskList {
     pub tasks: Vec<Task>,
@@ -42,7 +75,7 @@ pub struct Page {
 
 #[derive(Serialize, Default)]
 pub struct SaveResult {
-    success: bool,
+    pub success: bool,
 
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub error: Option<String>, // 错误信息
@@ -70,49 +103,101 @@ pub struct TaskParams{
     pub status: Option<String>,     // 状态 0:所有, 1:未开始, 2:完成
 }
 
+impl TaskParams {
+    // 今天的任务
+    pub fn today(status: &str) -> Self {
+        TaskParams {
+            start: Some(get_today_begin_time()),
+            end: Some(get_today_end_time()),
+            status: Some(status.to_string()),
+        }
+    }
+}
+
 #[tauri::command]
 pub fn save_tasks(tasks: TaskList, app: tauri::AppHandle) -> Result<SaveResult, String> {
-    save_tasks_impl(&tasks, &app)
+    save_tasks_impl(&tasks, app.core())
 }
 
 #[tauri::command]
 pub async fn add_task(task: Task, app: tauri::AppHandle) -> Result<SaveResult, String> {
-    add_task_to_notion_impl(&task, &app).await
+    add_task_impl(&task, app.core()).await
 }
 
 #[tauri::command]
 pub async fn update_task(task: Task, app: tauri::AppHandle) -> Result<SaveResult, String> {
-    update_task_in_notion_impl(&task, &app).await
+    update_task_impl(&task, app.core()).await
+}
+
+// 保存任务后发送 webhook，并通知界面更新托盘菜单，批量导入使用 add_task_to_notion_impl
+pub async fn add_task_impl(task: &Task, core: &TaskReminderCore) -> Result<SaveResult, String> {
+    let result = add_task_to_notion_impl(task, core).await?;
+    if result.success {
+        let mut created = task.clone();
+        created.id = result.id.clone().unwrap_or_default();
+        webhook::fire(core, WebhookEvent::TaskCreated, Some(&created), None);
+        core.emit("task_saved", &created);
+    }
+    Ok(result)
+}
+
+// 保存任务后通知界面更新托盘菜单
+// 只有从未完成变为完成时才记录完成情况、发送 webhook、执行钩子，修改已完成的任务不会重复触发
+pub async fn update_task_impl(task: &Task, core: &TaskReminderCore) -> Result<SaveResult, String> {
+    let completed = task.is_done() && !was_done(task, core).await;
+    let result = update_task_in_notion_impl(task, core).await?;
+    if result.success {
+        if completed {
+            record_task_completed(core);
+            webhook::fire(core, WebhookEvent::TaskCompleted, Some(task), None);
+            hooks::fire(core, HookEvent::TaskCompleted, Some(task), serde_json::Value::Null);
+            core.emit("task_completed", task);
+        }
+        core.emit("task_saved", task);
+    }
+    Ok(result)
+}
+
+// 保存前任务在 notion 中是否已经完成，读取失败时按未完成处理
+async fn was_done(task: &Task, core: &TaskReminderCore) -> bool {
+    if !is_task_id(&task.id) {
+        return false;
+    }
+    match load_task_from_notion_impl(&task.id, core).await {
+        Ok(previous) => previous.is_some_and(|previous| previous.is_done()),
+        Err(e) => {
+            log::warn!("load previous task error: {}", e);
+            false
+        }
+    }
 }
 
 #[tauri::command]
 pub async fn load_tasks(params: Option<TaskParams>, app: tauri::AppHandle) -> Result<SaveResult, String> {
-    load_tasks_impl(&params, &app).await
+    load_tasks_impl(&params, app.core()).await
 }
 
-pub async fn load_tasks_impl(params: &Option<TaskParams>, app: &tauri::AppHandle) -> Result<SaveResult, String> {
-    // let auth_info = get_auth_info_from_global();
+pub async fn load_tasks_impl(params: &Option<TaskParams>, core: &TaskReminderCore) -> Result<SaveResult, String> {
+    // let auth_info = core.auth_info();
     // if let Some(_auth) = auth_info {
     //     return load_tasks_from_notion_impl(&app).await;
     // } else {
     //     return load_tasks_from_local_impl(&app).await;
     // }
-    let auth_info = get_auth_info_from_global();
+    let auth_info = core.auth_info();
     if let Some(_auth) = auth_info {
-        return load_tasks_from_notion_impl(params, &app).await;
+        load_tasks_from_notion_impl(params, core).await
     } else {
-        return Ok(SaveResult {
+        Ok(SaveResult {
             success: false,
             status: Some("unauthorized".to_string()),
             ..Default::default()
-        });
+        })
     }
 }
 
-fn save_tasks_impl(tasks: &TaskList, app: &tauri::AppHandle) -> Result<SaveResult, String> {
-    let config_dir = load_setting_impl(app).unwrap().path;
-    let file_path = std::path::Path::new(&config_dir).join("tasks.json");
-    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+fn save_tasks_impl(tasks: &TaskList, core: &TaskReminderCore) -> Result<SaveResult, String> {
+    let file_path = core.data_file("tasks.json")?;
     let json = serde_json::to_string_pretty(&tasks).map_err(|e| e.to_string())?;
     std::fs::write(&file_path, json).map_err(|e| e.to_string())?;
     Ok(SaveResult {
@@ -122,12 +207,12 @@ fn save_tasks_impl(tasks: &TaskList, app: &tauri::AppHandle) -> Result<SaveResul
 }
 
 // 从notion加载任务
-pub async fn load_tasks_from_notion_impl(params: &Option<TaskParams>, _app: &tauri::AppHandle) -> Result<SaveResult, String> {
-    let auth_info = get_auth_info_from_global();
+pub async fn load_tasks_from_notion_impl(params: &Option<TaskParams>, core: &TaskReminderCore) -> Result<SaveResult, String> {
+    let auth_info = core.auth_info();
     if let Some(auth) = auth_info {
         let url = format!(
             "{}/v1/databases/{}/query",
-            std::env::var("VITE_NOTION_API_URL").unwrap_or_default(),
+            core.notion_api_url(),
             auth.duplicated_template_id
         );
 
@@ -140,110 +225,168 @@ pub async fn load_tasks_from_notion_impl(params: &Option<TaskParams>, _app: &tau
         headers.insert("Content-Type", HeaderValue::from_static("application/json"));
 
 
-        let search_condition = get_search_condition(params);
-        
-        let res = reqwest::Client::new()
-            .post(url)
-            .headers(headers)
-            .body(serde_json::to_string(&search_condition).unwrap())
-            .send()
-            .await;
-
-        match res {
-            Ok(res) => {
-                let text = res.text().await.unwrap();
-                //println!("res text: {:?}", text);
-                let json: serde_json::Value = serde_json::from_str(&text).unwrap();
-                let results = match json.get("results").and_then(|v| v.as_array()) {
-                    Some(arr) => arr,
-                    None => {
-                        println!("解析Notion返回结果失败: {:?}", json);
-                        return Ok(SaveResult {
-                            success: false,
-                            status: Some(json["code"].as_str().unwrap_or_default().to_string()),
-                            ..Default::default()
-                        });
-                    }
-                };
-                let tasks: Vec<Task> = results
-                    .iter()
-                    .map(|result| {
-                        let id = result["id"].as_str().unwrap_or_default();
-                        let text = result["properties"]["task"]["title"]
-                            .as_array()
-                            .and_then(|arr| arr.get(0))
-                            .and_then(|item| item["plain_text"].as_str())
-                            .unwrap_or_default();
-
-                        let percent = result["properties"]["percent"]["number"]
-                            .as_u64()
-                            .unwrap_or(0) as u32;
-
-                        let status = result["properties"]["status"]["status"]["name"]
-                            .as_str()
-                            .unwrap_or_default();
-
-                        let time = Time {
-                            start: result["properties"]["time"]["date"]["start"]
-                                .as_str()
-                                .unwrap_or_default()
-                                .to_string(),
-                            end: result["properties"]["time"]["date"]["end"]
-                                .as_str()
-                                .map(|s| s.to_string()),
-                            time_zone: result["properties"]["time"]["date"]["time_zone"]
-                                .as_str()
-                                .map(|s| s.to_string()),
-                        };
-
-                        let tags = match result.get("properties").and_then(|v| v.get("tags")) {
-                            Some(v) => v.get("multi_select").and_then(|v| v.as_array()).map(|v| v.iter().map(|v| v["name"].as_str().unwrap_or_default().to_string()).collect::<Vec<_>>()),
-                            None => None,
-                        };
-                        
-                        Task {
-                            id: id.to_string(),
-                            text: text.to_string(),
-                            percent: percent as u32,
-                            status: status.to_string(),
-                            time: time,
-                            tags: tags,
-                        }
-                    })
-                    .collect();
+        // 每次最多返回 100 条，按 next_cursor 继续查询，直到 has_more 为 false
+        let mut search_condition = get_search_condition(params);
+        let mut tasks: Vec<Task> = vec![];
+        loop {
+            let res = reqwest::Client::new()
+                .post(&url)
+                .headers(headers.clone())
+                .body(serde_json::to_string(&search_condition).unwrap())
+                .send()
+                .await;
+
+            let text = match res {
+                Ok(res) => res.text().await.map_err(|e| e.to_string())?,
+                Err(e) => {
+                    log::error!("load tasks error: {:?}", e);
+                    // 只返回部分任务会被当作全部任务，查询失败时不返回结果
+                    return Ok(SaveResult {
+                        success: false,
+                        status: Some("network_error".to_string()),
+                        ..Default::default()
+                    });
+                }
+            };
+            //println!("res text: {:?}", text);
+            let json: serde_json::Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+            let results = match json.get("results").and_then(|v| v.as_array()) {
+                Some(arr) => arr,
+                None => {
+                    log::error!("解析Notion返回结果失败: {:?}", json);
+                    return Ok(SaveResult {
+                        success: false,
+                        status: Some(json["code"].as_str().unwrap_or_default().to_string()),
+                        ..Default::default()
+                    });
+                }
+            };
+            tasks.extend(results.iter().map(task_from_page));
 
-                return Ok(SaveResult {
-                    success: true,
-                    tasks: Some(TaskList { tasks: tasks }),
-                    ..Default::default()
-                });
-            }
-            Err(e) => {
-                println!("error: {:?}", e);
+            match json["next_cursor"].as_str() {
+                Some(cursor) if json["has_more"].as_bool().unwrap_or(false) => {
+                    search_condition["start_cursor"] = json!(cursor);
+                }
+                _ => break,
             }
         }
 
+        return Ok(SaveResult {
+            success: true,
+            tasks: Some(TaskList { tasks }),
+            ..Default::default()
+        });
         //let body = res.json::<TaskList>().await?;
     }
-    return Ok(SaveResult {
+    Ok(SaveResult {
         success: true,
         tasks: Some(TaskList { tasks: vec![] }),
         ..Default::default()
-    });
+    })
+}
+
+// notion 页面转换为任务
+fn task_from_page(result: &serde_json::Value) -> Task {
+    let id = result["id"].as_str().unwrap_or_default();
+    let text = result["properties"]["task"]["title"]
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|item| item["plain_text"].as_str())
+        .unwrap_or_default();
+
+    let percent = result["properties"]["percent"]["number"]
+        .as_u64()
+        .unwrap_or(0) as u32;
+
+    let status = result["properties"]["status"]["status"]["name"]
+        .as_str()
+        .unwrap_or_default();
+
+    let time = Time {
+        start: result["properties"]["time"]["date"]["start"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        end: result["properties"]["time"]["date"]["end"]
+            .as_str()
+            .map(|s| s.to_string()),
+        time_zone: result["properties"]["time"]["date"]["time_zone"]
+            .as_str()
+            .map(|s| s.to_string()),
+    };
+
+    let tags = match result.get("properties").and_then(|v| v.get("tags")) {
+        Some(v) => v.get("multi_select").and_then(|v| v.as_array()).map(|v| v.iter().map(|v| v["name"].as_str().unwrap_or_default().to_string()).collect::<Vec<_>>()),
+        None => None,
+    };
+
+    Task {
+        id: id.to_string(),
+        text: text.to_string(),
+        percent,
+        status: status.to_string(),
+        time,
+        tags,
+    }
+}
+
+// notion 页面 id 为 32 位十六进制，可能带连字符
+pub fn is_task_id(id: &str) -> bool {
+    id.len() <= 36
+        && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        && id.chars().filter(|c| *c != '-').count() == 32
+}
+
+// 按 id 从notion加载一条任务，任务不存在或已删除时返回 None
+pub async fn load_task_from_notion_impl(
+    id: &str,
+    core: &TaskReminderCore,
+) -> Result<Option<Task>, String> {
+    let Some(auth) = core.auth_info() else {
+        return Err("unauthorized".to_string());
+    };
+    let url = format!(
+        "{}/v1/pages/{}",
+        core.notion_api_url(),
+        id
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_str(&format!("Bearer {}", auth.access_token)).map_err(|e| e.to_string())?,
+    );
+    headers.insert("Notion-Version", HeaderValue::from_static("2022-06-28"));
+
+    let res = reqwest::Client::new()
+        .get(url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let text = res.text().await.map_err(|e| e.to_string())?;
+    let json: serde_json::Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+
+    match json["object"].as_str() {
+        Some("page") if !json["archived"].as_bool().unwrap_or(false) => Ok(Some(task_from_page(&json))),
+        Some("page") => Ok(None),
+        _ if json["code"] == "object_not_found" => Ok(None),
+        _ => Err(json["message"].as_str().unwrap_or("load task failed").to_string()),
+    }
 }
 
 // 修改notion中的某条任务
-async fn update_task_in_notion_impl(
+pub async fn update_task_in_notion_impl(
     task: &Task,
-    _app: &tauri::AppHandle,
+    core: &TaskReminderCore,
 ) -> Result<SaveResult, String> {
     log::info!("update_task_in_notion_impl");
-    let auth_info = get_auth_info_from_global();
+    let auth_info = core.auth_info();
     if let Some(auth) = auth_info {
         // 使用 auth
         let url = format!(
             "{}/v1/pages/{}",
-            std::env::var("VITE_NOTION_API_URL").unwrap_or_default(),
+            core.notion_api_url(),
             task.id
         );
 
@@ -290,10 +433,10 @@ async fn update_task_in_notion_impl(
         });
 
         
-        if task.tags.is_some() {
-            log::info!("update_task_in_notion_impl task.tags: {:?}", task.tags);
+        if let Some(tags) = &task.tags {
+            log::info!("update_task_in_notion_impl task.tags: {:?}", tags);
             body["properties"]["tags"] = json!({
-                "multi_select":  task.tags.as_ref().unwrap().iter().map(|v| json!({
+                "multi_select":  tags.iter().map(|v| json!({
                         "name": v
                     })).collect::<Vec<_>>()
                 
@@ -325,21 +468,21 @@ async fn update_task_in_notion_impl(
         }
     }
 
-    return Ok(SaveResult {
+    Ok(SaveResult {
         success: false,
         ..Default::default()
-    });
+    })
 }
 
-async fn add_task_to_notion_impl(
+pub async fn add_task_to_notion_impl(
     task: &Task,
-    _app: &tauri::AppHandle,
+    core: &TaskReminderCore,
 ) -> Result<SaveResult, String> {
-    let auth_info = get_auth_info_from_global();
+    let auth_info = core.auth_info();
     if let Some(auth) = auth_info {
         let url = format!(
             "{}/v1/pages",
-            std::env::var("VITE_NOTION_API_URL").unwrap_or_default()
+            core.notion_api_url()
         );
 
         let mut headers = HeaderMap::new();
@@ -388,9 +531,9 @@ async fn add_task_to_notion_impl(
             }
         });
 
-        if task.tags.is_some() {
+        if let Some(tags) = &task.tags {
             body["properties"]["tags"] = json!({
-                "multi_select":  task.tags.as_ref().unwrap().iter().map(|v| json!({
+                "multi_select":  tags.iter().map(|v| json!({
                         "name": v
                     })).collect::<Vec<_>>()
                 
@@ -409,11 +552,19 @@ async fn add_task_to_notion_impl(
 
         match res {
             Ok(res) => {
-                let text = res.text().await.unwrap();
-                let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let text = res.text().await.map_err(|e| e.to_string())?;
+                let json: serde_json::Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+                // 频率限制等错误时没有 id，返回 notion 的错误信息
+                let Some(id) = json["id"].as_str() else {
+                    return Ok(SaveResult {
+                        success: false,
+                        error: Some(json["message"].as_str().unwrap_or("create task failed").to_string()),
+                        ..Default::default()
+                    });
+                };
                 return Ok(SaveResult {
                     success: true,
-                    id: Some(json.get("id").unwrap().as_str().unwrap().to_string()),
+                    id: Some(id.to_string()),
                     ..Default::default()
                 });
             }
@@ -423,10 +574,10 @@ async fn add_task_to_notion_impl(
         }
     }
 
-    return Ok(SaveResult {
+    Ok(SaveResult {
         success: false,
         ..Default::default()
-    });
+    })
 }
 
 // 获取今天0点的时间
@@ -441,9 +592,7 @@ fn get_today_begin_time() -> String {
     let midnight_local = Local.from_local_datetime(&midnight_naive).unwrap();
 
     // 4. 输出 ISO8601 格式字符串
-    let iso8601_str = midnight_local.to_rfc3339();
-
-    return iso8601_str;
+    midnight_local.to_rfc3339()
 }
 
 // 获取今天0点的时间
@@ -458,21 +607,19 @@ fn get_today_end_time() -> String {
     let midnight_local = Local.from_local_datetime(&midnight_naive).unwrap();
 
     // 4. 输出 ISO8601 格式字符串
-    let iso8601_str = midnight_local.to_rfc3339();
-
-    return iso8601_str;
+    midnight_local.to_rfc3339()
 }
 
 
 #[tauri::command]
 pub async fn load_pages(app: tauri::AppHandle) -> Result<SaveResult, String> {
-    load_pages_from_notion_impl(&app).await
+    load_pages_from_notion_impl(app.core()).await
 }
 
 // 从notion加载任务
-pub async fn load_pages_from_notion_impl(_app: &tauri::AppHandle) -> Result<SaveResult, String> {
+pub async fn load_pages_from_notion_impl(core: &TaskReminderCore) -> Result<SaveResult, String> {
     log::info!("load_pages_from_notion_impl");
-    let auth_info = get_auth_info_from_global();
+    let auth_info = core.auth_info();
     log::debug!(
         "load_pages_from_notion_impl auth_info: {:?}",
         auth_info.is_some()
@@ -485,7 +632,7 @@ pub async fn load_pages_from_notion_impl(_app: &tauri::AppHandle) -> Result<Save
 
         let url = format!(
             "{}/v1/search",
-            std::env::var("VITE_NOTION_API_URL").unwrap_or_default()
+            core.notion_api_url()
         );
 
         log::debug!("VITE_NOTION_API_URL: {:?}", url);
@@ -540,21 +687,19 @@ pub async fn load_pages_from_notion_impl(_app: &tauri::AppHandle) -> Result<Save
                         let id = result["id"].as_str().unwrap_or_default().replace("-", "");
                         let object = result["object"].as_str().unwrap_or_default();
 
-                        let title;
-
-                        if object == "page" {
-                            title = result["properties"]["title"]["title"]
+                        let title = if object == "page" {
+                            result["properties"]["title"]["title"]
                                 .as_array()
-                                .and_then(|arr| arr.get(0))
+                                .and_then(|arr| arr.first())
                                 .and_then(|item| item["plain_text"].as_str())
-                                .unwrap_or_default();
+                                .unwrap_or_default()
                         } else {
-                            title = result["title"]
+                            result["title"]
                                 .as_array()
-                                .and_then(|arr| arr.get(0))
+                                .and_then(|arr| arr.first())
                                 .and_then(|item| item["plain_text"].as_str())
-                                .unwrap_or_default();
-                        }
+                                .unwrap_or_default()
+                        };
 
                         let parent_type = result["parent"]["type"].as_str().unwrap_or_default();
                         let parent_id = result["parent"][parent_type]
@@ -592,12 +737,12 @@ pub async fn load_pages_from_notion_impl(_app: &tauri::AppHandle) -> Result<Save
         //let body = res.json::<TaskList>().await?;
     }
 
-    log::error!("load_pages_from_notion_impl get_auth_info_from_global failed");
-    return Ok(SaveResult {
+    log::error!("load_pages_from_notion_impl auth_info not found");
+    Ok(SaveResult {
         success: false,
         status: Some("unauthorized".to_string()),
         ..Default::default()
-    });
+    })
 }
 
 
@@ -646,23 +791,28 @@ fn get_search_condition(params: &Option<TaskParams>) -> serde_json::Value {
         .and_then(|v| v.as_array_mut()) 
         {
 
-            arr.push(json!(
-                {
-                    "property": "time",
-                    "date":{
-                        "on_or_after": start,
+            // 开始或结束日期为空时不限制，例如查询所有逾期任务
+            if !start.is_empty() {
+                arr.push(json!(
+                    {
+                        "property": "time",
+                        "date":{
+                            "on_or_after": start,
+                        }
                     }
-                }
-            ));  
+                ));
+            }
 
-            arr.push(json!(
-                {
-                    "property": "time",
-                    "date":{
-                        "on_or_before": end,
+            if !end.is_empty() {
+                arr.push(json!(
+                    {
+                        "property": "time",
+                        "date":{
+                            "on_or_before": end,
+                        }
                     }
-                }
-            ));
+                ));
+            }
 
 
             if status == "1" {
@@ -708,30 +858,30 @@ fn get_search_condition(params: &Option<TaskParams>) -> serde_json::Value {
         }
     }
 
-    return body
+    body
 
 }
 
 
 #[tauri::command]
-pub async fn load_tags() -> Result<SaveResult, String> {
+pub async fn load_tags(app: tauri::AppHandle) -> Result<SaveResult, String> {
     log::info!("load_tags");
-    load_tags_impl().await
+    load_tags_impl(app.core()).await
 }
 
 #[tauri::command]
-pub async fn update_tags(tags: Vec<String>) -> Result<SaveResult, String> {
+pub async fn update_tags(tags: Vec<String>, app: tauri::AppHandle) -> Result<SaveResult, String> {
     log::info!("update_tags");
-    update_tags_impl(&tags ).await
+    update_tags_impl(&tags, app.core()).await
 }
-async fn load_tags_impl() -> Result<SaveResult, String> {
+pub async fn load_tags_impl(core: &TaskReminderCore) -> Result<SaveResult, String> {
     log::info!("load_tags_impl");
-    let auth_info = get_auth_info_from_global();
+    let auth_info = core.auth_info();
 
     if let Some(auth) = auth_info {
         let url = format!(
             "{}/v1/databases/{}",
-            std::env::var("VITE_NOTION_API_URL").unwrap_or_default(),
+            core.notion_api_url(),
             auth.duplicated_template_id
         );
 
@@ -814,23 +964,23 @@ async fn load_tags_impl() -> Result<SaveResult, String> {
         //let body = res.json::<TaskList>().await?;
     }
 
-    log::error!("load_pages_from_notion_impl get_auth_info_from_global failed");
+    log::error!("load_pages_from_notion_impl auth_info not found");
 
-    return Ok(SaveResult {
+    Ok(SaveResult {
         success: false,
         tags: Some(vec![]),
         ..Default::default()
-    });
+    })
 }
 
-async fn update_tags_impl(tags: &Vec<String>) -> Result<SaveResult, String>  {
+async fn update_tags_impl(tags: &[String], core: &TaskReminderCore) -> Result<SaveResult, String>  {
     log::info!("update_tags_impl");
-    let auth_info = get_auth_info_from_global();
+    let auth_info = core.auth_info();
 
     if let Some(auth) = auth_info {
         let url = format!(
             "{}/v1/databases/{}",
-            std::env::var("VITE_NOTION_API_URL").unwrap_or_default(),
+            core.notion_api_url(),
             auth.duplicated_template_id
         );
 
@@ -878,7 +1028,7 @@ async fn update_tags_impl(tags: &Vec<String>) -> Result<SaveResult, String>  {
                         let error = json.get("message").and_then(|v| v.as_str()).map(|v| v.to_string());
                         return Ok(SaveResult {
                             success: false,
-                            error: error,
+                            error,
                             ..Default::default()
                         });
                     }
@@ -928,11 +1078,156 @@ async fn update_tags_impl(tags: &Vec<String>) -> Result<SaveResult, String>  {
         //let body = res.json::<TaskList>().await?;
     }
 
-    log::error!("load_pages_from_notion_impl get_auth_info_from_global failed");
+    log::error!("load_pages_from_notion_impl auth_info not found");
 
-    return Ok(SaveResult {
+    Ok(SaveResult {
         success: false,
         tags: Some(vec![]),
         ..Default::default()
-    });
-}
\ No newline at end of file
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_notion::{event_names, test_task, MockNotion};
+    use crate::slash_command::{execute, SlashCommand};
+    use tauri::async_runtime::block_on;
+
+    #[test]
+    fn load_tasks_requires_login() {
+        let notion = MockNotion::start();
+        let (core, _) = notion.logged_out_core();
+        let result = block_on(load_tasks_impl(&None, &core)).unwrap();
+        assert!(!result.success);
+        assert_eq!(result.status.as_deref(), Some("unauthorized"));
+        assert!(notion.requests().is_empty());
+    }
+
+    #[test]
+    fn add_task_creates_page_and_notifies() {
+        let notion = MockNotion::start();
+        let (core, events) = notion.core();
+        let task = test_task("写周报", "未开始", "2026-10-19");
+
+        let result = block_on(add_task_impl(&task, &core)).unwrap();
+        assert!(result.success);
+        let id = result.id.unwrap();
+        assert_eq!(notion.task(&id).unwrap().text, "写周报");
+        assert_eq!(event_names(&events), ["task_saved"]);
+        assert_eq!(events.lock().unwrap()[0].1["id"], id.as_str());
+    }
+
+    #[test]
+    fn update_task_marks_done_and_notifies() {
+        let notion = MockNotion::start();
+        let (core, events) = notion.core();
+        let mut task = test_task("写周报", "未开始", "2026-10-19");
+        task.id = notion.add_page(&task);
+
+        task.mark_done();
+        let result = block_on(update_task_impl(&task, &core)).unwrap();
+        assert!(result.success);
+        assert!(notion.task(&task.id).unwrap().is_done());
+        assert_eq!(event_names(&events), ["task_completed", "task_saved"]);
+    }
+
+    #[test]
+    fn update_done_task_does_not_complete_again() {
+        let notion = MockNotion::start();
+        let (core, events) = notion.core();
+        let mut task = test_task("写周报", "完成", "2026-10-19");
+        task.id = notion.add_page(&task);
+
+        task.text = "写周报和月报".to_string();
+        let result = block_on(update_task_impl(&task, &core)).unwrap();
+        assert!(result.success);
+        assert_eq!(notion.task(&task.id).unwrap().text, "写周报和月报");
+        assert_eq!(event_names(&events), ["task_saved"]);
+    }
+
+    #[test]
+    fn load_tasks_reads_pages() {
+        let notion = MockNotion::start();
+        let (core, _) = notion.core();
+        notion.add_page(&test_task("写周报", "未开始", "2026-10-19"));
+        notion.add_page(&test_task("开会", "完成", "2026-10-19T15:00:00+08:00"));
+
+        let result = block_on(load_tasks_impl(&Some(TaskParams::today("0")), &core)).unwrap();
+        let tasks = result.tasks.unwrap().tasks;
+        let texts: Vec<&str> = tasks.iter().map(|task| task.text.as_str()).collect();
+        assert_eq!(texts, ["写周报", "开会"]);
+        assert!(tasks[1].is_done());
+    }
+
+    #[test]
+    fn load_tasks_follows_next_cursor() {
+        let notion = MockNotion::start();
+        let (core, _) = notion.core();
+        notion.set_page_size(2);
+        for i in 0..5 {
+            notion.add_page(&test_task(&format!("任务{}", i), "未开始", "2026-10-19"));
+        }
+
+        let result = block_on(load_tasks_impl(&Some(TaskParams::today("0")), &core)).unwrap();
+        assert_eq!(result.tasks.unwrap().tasks.len(), 5);
+        let queries = notion.requests().iter().filter(|r| r.ends_with("/query")).count();
+        assert_eq!(queries, 3);
+    }
+
+    #[test]
+    fn start_date_accepts_all_day_tasks() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 19);
+        assert_eq!(test_task("写周报", "未开始", "2026-10-19").start_date(), day);
+        let local = Local.from_local_datetime(&day.unwrap().and_hms_opt(15, 0, 0).unwrap()).unwrap();
+        assert_eq!(test_task("开会", "未开始", &local.to_rfc3339()).start_date(), day);
+        assert_eq!(test_task("想法", "未开始", "").start_date(), None);
+    }
+
+    #[test]
+    fn is_done_accepts_both_languages() {
+        assert!(test_task("写周报", "完成", "").is_done());
+        assert!(test_task("写周报", "Done", "").is_done());
+        assert!(!test_task("写周报", "进行中", "").is_done());
+        assert!(!test_task("写周报", "Not started", "").is_done());
+        assert!(!test_task("写周报", "", "").is_done());
+    }
+
+    #[test]
+    fn task_id_requires_32_hex_digits() {
+        assert!(is_task_id("1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d"));
+        assert!(is_task_id("1a2b3c4d-5e6f-7a8b-9c0d-1e2f3a4b5c6d"));
+        assert!(!is_task_id(""));
+        assert!(!is_task_id("---"));
+        assert!(!is_task_id("1a2b3c4d"));
+        assert!(!is_task_id("1a2b3c4d-5e6f-7a8b-9c0d-1e2f3a4b5c6g"));
+    }
+
+    #[test]
+    fn slash_done_completes_task_by_index() {
+        let notion = MockNotion::start();
+        let (core, events) = notion.core();
+        let first = notion.add_page(&test_task("写周报", "未开始", "2026-10-19"));
+        let second = notion.add_page(&test_task("开会", "未开始", "2026-10-19"));
+
+        let result = block_on(execute(SlashCommand::Done { index: 2 }, &core)).unwrap();
+        assert!(result.success, "{}", result.message);
+        assert!(!notion.task(&first).unwrap().is_done());
+        assert!(notion.task(&second).unwrap().is_done());
+        assert!(event_names(&events).contains(&"tasks_changed".to_string()));
+
+        let result = block_on(execute(SlashCommand::Done { index: 3 }, &core)).unwrap();
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn slash_snooze_sets_remind_later() {
+        let notion = MockNotion::start();
+        let (core, events) = notion.logged_out_core();
+
+        let result = block_on(execute(SlashCommand::Snooze { minutes: 30 }, &core)).unwrap();
+        assert!(result.success);
+        assert_eq!(core.remind_later(), 30);
+        assert_eq!(event_names(&events), ["reminder_snoozed"]);
+        assert!(notion.requests().is_empty());
+    }
+}
@@ -0,0 +1,731 @@
+// src-tauri/src/task_parser.rs
+// 解析自然语言输入的任务，例如 "call bank tomorrow 3pm #finance 30%"、"明天下午三点给银行打电话 #财务"
+// 识别日期、时间、时长、标签和权重，剩余部分作为任务内容
+use crate::task_manager::{Task, Time};
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Weekday};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Serialize;
+
+// 中文数字，最多到九十九
+const CN_NUM: &str = "[零一二两三四五六七八九十]+";
+
+static TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\s)#([^\s#]+)").unwrap());
+static PERCENT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\s)(\d{1,3})\s*%").unwrap());
+
+// in 2 hours / in 3 days / 2小时后 / 三天后
+static EN_RELATIVE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bin\s+(\d+|a|an|one|two|three|four|five|six|seven|eight|nine|ten)\s+(minutes?|mins?|hours?|hrs?|days?|weeks?)\b").unwrap()
+});
+static CN_RELATIVE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(半|\d+|{})\s*个?(分钟|小时|钟头|天|周|星期|礼拜)[之以]?后",
+        CN_NUM
+    ))
+    .unwrap()
+});
+
+// for 2h / 1h30m / 30 minutes / 2小时 / 一个半小时 / 45分钟
+static EN_DURATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:for\s+)?(?:(\d+(?:\.\d+)?)\s*(?:h|hrs?|hours?))?\s*(?:(\d+)\s*(?:m|mins?|minutes?))?\b").unwrap()
+});
+static CN_DURATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?:持续|用时|花)?(?:(半|\d+(?:\.\d+)?|{n})(个半|个)?(?:小时|钟头))?(?:(\d+|{n})分钟)?",
+        n = CN_NUM
+    ))
+    .unwrap()
+});
+
+// 2025-03-01 / 3/1 / 3月1日 / 2025年3月1号
+static ISO_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").unwrap());
+static SLASH_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:on\s+)?(\d{1,2})/(\d{1,2})\b").unwrap());
+static CN_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?:(\d{{4}})年)?(\d{{1,2}}|{n})月(\d{{1,2}}|{n})[日号號]",
+        n = CN_NUM
+    ))
+    .unwrap()
+});
+
+static EN_DAY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(day after tomorrow|today|tonight|tomorrow|tmrw?|next week|(?:this\s+)?weekend)\b",
+    )
+    .unwrap()
+});
+static CN_DAY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(大后天|后天|明天|明早|明晚|今天|今晚|下下周|下周|周末)").unwrap());
+
+// 星期只在 next / this / on / by 之后，或单独位于末尾时识别，避免 "buy sun cream" 中的 sun 被当作日期
+const EN_WEEKDAYS: &str = "mon|monday|tue|tues|tuesday|wed|wednesday|thu|thur|thurs|thursday|fri|friday|sat|saturday|sun|sunday";
+static EN_WEEKDAY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"(?i)\b(next|this|on|by)\s+({})\b", EN_WEEKDAYS)).unwrap());
+static EN_LAST_WEEKDAY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"(?i)(?:^|\s)({})\s*$", EN_WEEKDAYS)).unwrap());
+static CN_WEEKDAY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(下下|下|这|本)?(?:周|星期|礼拜)([一二三四五六日天])").unwrap());
+
+// 3pm / 3:30 pm / at 15:30 / at 3
+static EN_AMPM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:at\s+)?(\d{1,2})(?::(\d{2}))?\s*(am\b|pm\b|a\.m\.|p\.m\.)").unwrap()
+});
+static CLOCK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:\bat\s+)?\b([01]?\d|2[0-3])[:：]([0-5]\d)\b").unwrap());
+static EN_AT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bat\s+(\d{1,2})\b").unwrap());
+static EN_NOON_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:at\s+)?(noon|midnight)\b").unwrap());
+
+// 下午三点 / 晚上8点半 / 9点15分 / 上午十点一刻
+static CN_TIME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(早上|早晨|上午|中午|下午|傍晚|晚上|凌晨)?(\d{{1,2}}|{n})[点點](半|一刻|三刻|(\d{{1,2}}|{n})分?)?",
+        n = CN_NUM
+    ))
+    .unwrap()
+});
+
+// 只说了时段没有具体时间，例如 "tomorrow morning"、"明天下午"
+static PERIOD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:this\s+|in\s+the\s+)?(morning|afternoon|evening)\b|(早上|早晨|上午|中午|下午|傍晚|晚上)")
+        .unwrap()
+});
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Period {
+    EarlyMorning,
+    Morning,
+    Noon,
+    Afternoon,
+    Evening,
+}
+
+impl Period {
+    fn from_word(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "凌晨" => Some(Period::EarlyMorning),
+            "morning" | "早上" | "早晨" | "上午" => Some(Period::Morning),
+            "中午" => Some(Period::Noon),
+            "afternoon" | "下午" => Some(Period::Afternoon),
+            "evening" | "傍晚" | "晚上" => Some(Period::Evening),
+            _ => None,
+        }
+    }
+
+    // 没有具体时间时使用的默认时间
+    fn default_hour(&self) -> u32 {
+        match self {
+            Period::EarlyMorning => 6,
+            Period::Morning => 9,
+            Period::Noon => 12,
+            Period::Afternoon => 15,
+            Period::Evening => 20,
+        }
+    }
+
+    // 把 12 小时制的钟点转换为 24 小时制
+    fn to_24h(self, hour: u32) -> u32 {
+        match self {
+            Period::Afternoon | Period::Evening if hour < 12 => hour + 12,
+            Period::Noon if hour < 6 => hour + 12,
+            Period::EarlyMorning | Period::Morning if hour == 12 => 0,
+            _ => hour,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ParsedTask {
+    pub task: Task,
+    pub has_date: bool, // 是否识别到日期或时间，未识别时 time.start 为当前时间
+}
+
+#[tauri::command]
+pub fn parse_task(text: String) -> Result<ParsedTask, String> {
+    Ok(parse_task_text(&text, Local::now()))
+}
+
+pub fn parse_task_text(text: &str, now: DateTime<Local>) -> ParsedTask {
+    let mut parser = Parser {
+        text: text.to_string(),
+        today: now.date_naive(),
+    };
+
+    let mut tags = vec![];
+    while let Some(tag) = parser.take(&TAG_RE, |c| Some(c[1].to_string())) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    let percent = parser
+        .take(&PERCENT_RE, |c| c[1].parse::<u32>().ok())
+        .map(|p| p.min(100));
+
+    // 相对时间，精确到分钟的直接得到开始时间，按天的得到日期
+    let mut exact = None;
+    let mut date = None;
+    let relative = parser
+        .take(&EN_RELATIVE_RE, |c| relative_offset(&c[1], &c[2]))
+        .or_else(|| parser.take(&CN_RELATIVE_RE, |c| relative_offset(&c[1], &c[2])));
+    match relative {
+        Some(Offset::Minutes(minutes)) => exact = Some(now + Duration::minutes(minutes)),
+        Some(Offset::Days(days)) => date = Some(parser.today + Duration::days(days)),
+        None => {}
+    }
+
+    let duration = take_duration(&mut parser);
+
+    let mut period = None;
+    if date.is_none() {
+        date = parser.take(&ISO_DATE_RE, |c| {
+            NaiveDate::from_ymd_opt(c[1].parse().ok()?, c[2].parse().ok()?, c[3].parse().ok()?)
+        });
+    }
+    if date.is_none() {
+        let today = parser.today;
+        date = parser.take(&CN_DATE_RE, |c| {
+            let year = match c.get(1) {
+                Some(year) => year.as_str().parse().ok()?,
+                None => today.year(),
+            };
+            let month = parse_number(&c[2])? as u32;
+            let day = parse_number(&c[3])? as u32;
+            NaiveDate::from_ymd_opt(year, month, day)
+        });
+    }
+    if date.is_none() {
+        let today = parser.today;
+        date = parser.take(&SLASH_DATE_RE, |c| {
+            NaiveDate::from_ymd_opt(today.year(), c[1].parse().ok()?, c[2].parse().ok()?)
+        });
+    }
+    if date.is_none() {
+        let today = parser.today;
+        date = parser
+            .take(&EN_WEEKDAY_RE, |c| {
+                let weeks = if c[1].eq_ignore_ascii_case("next") {
+                    1
+                } else {
+                    0
+                };
+                Some(weekday_date(today, en_weekday(&c[2])?, weeks))
+            })
+            .or_else(|| {
+                parser.take(&CN_WEEKDAY_RE, |c| {
+                    let weekday = cn_weekday(&c[2])?;
+                    let date = match c.get(1).map(|m| m.as_str()) {
+                        Some("下下") => weekday_date(today, weekday, 2),
+                        Some("下") => weekday_date(today, weekday, 1),
+                        Some(_) => {
+                            week_start(today)
+                                + Duration::days(weekday.num_days_from_monday() as i64)
+                        }
+                        None => weekday_date(today, weekday, 0),
+                    };
+                    Some(date)
+                })
+            });
+    }
+
+    if date.is_none() {
+        let today = parser.today;
+        let day = parser
+            .take(&EN_DAY_RE, |c| day_word(&c[1].to_lowercase(), today))
+            .or_else(|| parser.take(&CN_DAY_RE, |c| day_word(&c[1], today)));
+        if let Some((day, hint)) = day {
+            date = Some(day);
+            period = hint;
+        }
+    }
+
+    let mut time = None;
+    if exact.is_none() {
+        time = parser
+            .take(&EN_AMPM_RE, |c| {
+                let hour: u32 = c[1].parse().ok()?;
+                let minute: u32 = c.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+                let pm = c[3].to_lowercase().starts_with('p');
+                let hour = match (hour, pm) {
+                    (12, false) => 0,
+                    (12, true) => 12,
+                    (hour, true) => hour + 12,
+                    (hour, false) => hour,
+                };
+                NaiveTime::from_hms_opt(hour, minute, 0)
+            })
+            .or_else(|| {
+                parser.take(&CLOCK_RE, |c| {
+                    NaiveTime::from_hms_opt(c[1].parse().ok()?, c[2].parse().ok()?, 0)
+                })
+            })
+            .or_else(|| {
+                parser.take(&EN_NOON_RE, |c| match c[1].to_lowercase().as_str() {
+                    "noon" => NaiveTime::from_hms_opt(12, 0, 0),
+                    _ => NaiveTime::from_hms_opt(23, 59, 0),
+                })
+            })
+            .or_else(|| {
+                parser.take(&CN_TIME_RE, |c| {
+                    // "快一点" 之类不是时间
+                    if c.get(1).is_none() && &c[2] == "一" && c.get(3).is_none() {
+                        return None;
+                    }
+                    let hour = parse_number(&c[2])? as u32;
+                    let minute = match c.get(3).map(|m| m.as_str()) {
+                        Some("半") => 30,
+                        Some("一刻") => 15,
+                        Some("三刻") => 45,
+                        Some(_) => parse_number(&c[4])? as u32,
+                        None => 0,
+                    };
+                    let period = c
+                        .get(1)
+                        .and_then(|m| Period::from_word(m.as_str()))
+                        .or(period);
+                    NaiveTime::from_hms_opt(guess_hour(hour, period), minute, 0)
+                })
+            })
+            .or_else(|| {
+                parser.take(&EN_AT_RE, |c| {
+                    let hour: u32 = c[1].parse().ok()?;
+                    NaiveTime::from_hms_opt(guess_hour(hour, period), 0, 0)
+                })
+            });
+    }
+    if exact.is_none() && time.is_none() {
+        let word = parser.take(&PERIOD_RE, |c| {
+            Period::from_word(c.get(1).or(c.get(2))?.as_str())
+        });
+        if let Some(word) = word.or(period) {
+            time = NaiveTime::from_hms_opt(word.default_hour(), 0, 0);
+        }
+    }
+    // 时间已从文本中移除，末尾剩下的星期作为日期，例如 "report fri 3pm"
+    if exact.is_none() && date.is_none() {
+        let today = parser.today;
+        date = parser.take(&EN_LAST_WEEKDAY_RE, |c| {
+            Some(weekday_date(today, en_weekday(&c[1])?, 0))
+        });
+    }
+
+    let has_date = exact.is_some() || date.is_some() || time.is_some();
+    let start = match (exact, date, time) {
+        (Some(exact), _, _) => Some(exact),
+        (None, date, Some(time)) => to_local(date.unwrap_or(parser.today), time),
+        // 只有日期时作为全天任务，有时长时从当前时间或 9 点开始
+        (None, Some(date), None) if duration.is_some() => {
+            if date == parser.today {
+                Some(now)
+            } else {
+                to_local(date, NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+            }
+        }
+        (None, Some(_), None) => None,
+        (None, None, None) => Some(now),
+    };
+
+    let time = match (start, date) {
+        (Some(start), _) => Time {
+            start: format_time(&start),
+            end: duration.map(|minutes| format_time(&(start + Duration::minutes(minutes)))),
+            time_zone: None,
+        },
+        (None, date) => Time {
+            start: date.unwrap_or(parser.today).format("%Y-%m-%d").to_string(),
+            end: None,
+            time_zone: None,
+        },
+    };
+
+    ParsedTask {
+        task: Task {
+            id: "".to_string(),
+            text: parser.rest(),
+            percent: percent.unwrap_or(0),
+            status: "未开始".to_string(),
+            time,
+            tags: Some(tags),
+        },
+        has_date,
+    }
+}
+
+/// 由外部传入的任务内容、标签和日期创建任务，用于链接和本地接口
+/// 任务内容中的日期、标签也会被识别，date 参数优先
+pub fn build_task(
+    text: &str,
+    tags: Vec<String>,
+    date: Option<&str>,
+    now: DateTime<Local>,
+) -> Result<Task, String> {
+    let mut task = parse_task_text(text, now).task;
+    if task.text.is_empty() {
+        return Err("任务内容不能为空".to_string());
+    }
+
+    if let Some(date) = date {
+        task.time = parse_date(date, now).ok_or_else(|| format!("无法识别的日期: {}", date))?;
+    }
+
+    let mut all_tags = task.tags.take().unwrap_or_default();
+    for tag in tags {
+        if !all_tags.contains(&tag) {
+            all_tags.push(tag);
+        }
+    }
+    task.tags = Some(all_tags);
+    Ok(task)
+}
+
+// 只包含日期时间的文本，例如 "明天下午3点"，有其他内容时返回 None
+pub fn parse_date(text: &str, now: DateTime<Local>) -> Option<Time> {
+    let parsed = parse_task_text(text, now);
+    (parsed.has_date && parsed.task.text.is_empty()).then_some(parsed.task.time)
+}
+
+// 单独的一段时长，例如 "1h30m"、"45 minutes"、"半小时"，返回分钟数
+pub fn parse_duration(text: &str) -> Option<i64> {
+    let mut parser = Parser {
+        text: text.to_string(),
+        today: Local::now().date_naive(),
+    };
+    let minutes = take_duration(&mut parser)?;
+    parser.rest().is_empty().then_some(minutes)
+}
+
+fn take_duration(parser: &mut Parser) -> Option<i64> {
+    parser
+        .take(&EN_DURATION_RE, |c| {
+            duration_minutes(c.get(1).map(|m| m.as_str()), None, c.get(2))
+        })
+        .or_else(|| {
+            parser.take(&CN_DURATION_RE, |c| {
+                let half = c.get(2).is_some_and(|m| m.as_str() == "个半");
+                duration_minutes(c.get(1).map(|m| m.as_str()), Some(half), c.get(3))
+            })
+        })
+}
+
+// 解析过程中逐步从文本中移除已识别的部分
+struct Parser {
+    text: String,
+    today: NaiveDate,
+}
+
+impl Parser {
+    // 依次对非空的匹配调用 f，取第一个返回 Some 的结果并从文本中移除该匹配
+    fn take<T>(&mut self, re: &Regex, mut f: impl FnMut(&Captures) -> Option<T>) -> Option<T> {
+        let (value, range) = re.captures_iter(&self.text).find_map(|caps| {
+            let m = caps.get(0)?;
+            if m.as_str().trim().is_empty() {
+                return None;
+            }
+            Some((f(&caps)?, m.range()))
+        })?;
+        self.text.replace_range(range, " ");
+        Some(value)
+    }
+
+    // 剩余的任务内容，合并多余的空白
+    fn rest(&self) -> String {
+        self.text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+enum Offset {
+    Minutes(i64),
+    Days(i64),
+}
+
+fn relative_offset(amount: &str, unit: &str) -> Option<Offset> {
+    let amount = parse_number(amount)?;
+    let unit = unit.to_lowercase();
+    let offset = if unit.starts_with("min") || unit == "分钟" {
+        Offset::Minutes(amount as i64)
+    } else if unit.starts_with('h') || unit == "小时" || unit == "钟头" {
+        Offset::Minutes((amount * 60.0) as i64)
+    } else if unit.starts_with("day") || unit == "天" {
+        Offset::Days(amount as i64)
+    } else {
+        Offset::Days(amount as i64 * 7)
+    };
+    Some(offset)
+}
+
+// 时长的分钟数，half 表示 "一个半小时" 中的 "个半"
+fn duration_minutes(
+    hours: Option<&str>,
+    half: Option<bool>,
+    minutes: Option<regex::Match>,
+) -> Option<i64> {
+    let mut total = 0.0;
+    if let Some(hours) = hours {
+        total += parse_number(hours)? * 60.0;
+    }
+    if half == Some(true) {
+        total += 30.0;
+    }
+    if let Some(minutes) = minutes {
+        total += parse_number(minutes.as_str())?;
+    }
+    if total > 0.0 {
+        Some(total as i64)
+    } else {
+        None
+    }
+}
+
+// 今天、明天等，返回日期和附带的时段（今晚、明早）
+fn day_word(word: &str, today: NaiveDate) -> Option<(NaiveDate, Option<Period>)> {
+    let result = match word {
+        "today" | "今天" => (today, None),
+        "tonight" | "今晚" => (today, Some(Period::Evening)),
+        "tomorrow" | "tmr" | "tmrw" | "明天" => (today + Duration::days(1), None),
+        "明早" => (today + Duration::days(1), Some(Period::Morning)),
+        "明晚" => (today + Duration::days(1), Some(Period::Evening)),
+        "day after tomorrow" | "后天" => (today + Duration::days(2), None),
+        "大后天" => (today + Duration::days(3), None),
+        "next week" | "下周" => (week_start(today) + Duration::days(7), None),
+        "下下周" => (week_start(today) + Duration::days(14), None),
+        "周末" => (weekday_date(today, Weekday::Sat, 0), None),
+        word if word.ends_with("weekend") => (weekday_date(today, Weekday::Sat, 0), None),
+        _ => return None,
+    };
+    Some(result)
+}
+
+// 本周一
+fn week_start(today: NaiveDate) -> NaiveDate {
+    today - Duration::days(today.weekday().num_days_from_monday() as i64)
+}
+
+// weeks 为 0 时取今天或之后最近的一天，否则取之后第 weeks 周的那一天
+fn weekday_date(today: NaiveDate, weekday: Weekday, weeks: i64) -> NaiveDate {
+    if weeks > 0 {
+        return week_start(today)
+            + Duration::days(weeks * 7 + weekday.num_days_from_monday() as i64);
+    }
+    let ahead = (weekday.num_days_from_monday() as i64 + 7
+        - today.weekday().num_days_from_monday() as i64)
+        % 7;
+    today + Duration::days(ahead)
+}
+
+fn en_weekday(word: &str) -> Option<Weekday> {
+    let word = word.to_lowercase();
+    let weekday = match &word[..3] {
+        "mon" => Weekday::Mon,
+        "tue" => Weekday::Tue,
+        "wed" => Weekday::Wed,
+        "thu" => Weekday::Thu,
+        "fri" => Weekday::Fri,
+        "sat" => Weekday::Sat,
+        "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn cn_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "一" => Weekday::Mon,
+        "二" => Weekday::Tue,
+        "三" => Weekday::Wed,
+        "四" => Weekday::Thu,
+        "五" => Weekday::Fri,
+        "六" => Weekday::Sat,
+        "日" | "天" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+// 没有说明上下午时，1 到 6 点按下午处理
+fn guess_hour(hour: u32, period: Option<Period>) -> u32 {
+    match period {
+        Some(period) => period.to_24h(hour),
+        None if (1..=6).contains(&hour) => hour + 12,
+        None => hour,
+    }
+}
+
+// 阿拉伯数字、英文数字或中文数字
+fn parse_number(text: &str) -> Option<f64> {
+    if let Ok(number) = text.parse::<f64>() {
+        return Some(number);
+    }
+    let number = match text.to_lowercase().as_str() {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        "半" => return Some(0.5),
+        text => parse_cn_number(text)?,
+    };
+    Some(number as f64)
+}
+
+// 一、十二、二十、三十五、两
+fn parse_cn_number(text: &str) -> Option<u32> {
+    let digit = |c: char| match c {
+        '零' => Some(0),
+        '一' => Some(1),
+        '二' | '两' => Some(2),
+        '三' => Some(3),
+        '四' => Some(4),
+        '五' => Some(5),
+        '六' => Some(6),
+        '七' => Some(7),
+        '八' => Some(8),
+        '九' => Some(9),
+        _ => None,
+    };
+    match text.split_once('十') {
+        Some((tens, ones)) => {
+            let tens = match tens.chars().next() {
+                Some(c) => digit(c)?,
+                None => 1,
+            };
+            let ones = match ones.chars().next() {
+                Some(c) => digit(c)?,
+                None => 0,
+            };
+            Some(tens * 10 + ones)
+        }
+        None if text.chars().count() == 1 => digit(text.chars().next()?),
+        None => None,
+    }
+}
+
+fn to_local(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+// 与前端保存任务时的格式一致，例如 2025-03-01T15:00:00+08:00
+pub fn format_time(time: &DateTime<Local>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%:z").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-10-19 周一 10:00
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, 19, 10, 0, 0).unwrap()
+    }
+
+    // 全天任务为 "2026-10-20"，其他为本地时间 "2026-10-20 15:00"
+    fn start(task: &Task) -> String {
+        match DateTime::parse_from_rfc3339(&task.time.start) {
+            Ok(time) => time
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            Err(_) => task.time.start.clone(),
+        }
+    }
+
+    #[test]
+    fn parses_dates_times_tags_and_percent() {
+        let cases = [
+            // 输入，任务内容，开始时间，标签，权重
+            (
+                "call bank tomorrow 3pm #finance 30%",
+                "call bank",
+                "2026-10-20 15:00",
+                vec!["finance"],
+                30,
+            ),
+            (
+                "明天下午三点给银行打电话 #财务",
+                "给银行打电话",
+                "2026-10-20 15:00",
+                vec!["财务"],
+                0,
+            ),
+            (
+                "review plan next Mon",
+                "review plan",
+                "2026-10-26",
+                vec![],
+                0,
+            ),
+            (
+                "send invoice in 2 days",
+                "send invoice",
+                "2026-10-21",
+                vec![],
+                0,
+            ),
+            (
+                "meet Anna on wed at 10:30",
+                "meet Anna",
+                "2026-10-21 10:30",
+                vec![],
+                0,
+            ),
+            (
+                "team lunch this fri noon",
+                "team lunch",
+                "2026-10-23 12:00",
+                vec![],
+                0,
+            ),
+            (
+                "submit report by friday",
+                "submit report",
+                "2026-10-23",
+                vec![],
+                0,
+            ),
+            ("report fri 3pm", "report", "2026-10-23 15:00", vec![], 0),
+            ("water plants sat", "water plants", "2026-10-24", vec![], 0),
+            ("下周三 开会", "开会", "2026-10-28", vec![], 0),
+        ];
+        for (input, text, expected_start, tags, percent) in cases {
+            let parsed = parse_task_text(input, now());
+            assert!(parsed.has_date, "{}", input);
+            assert_eq!(parsed.task.text, text, "{}", input);
+            assert_eq!(start(&parsed.task), expected_start, "{}", input);
+            assert_eq!(parsed.task.tags.unwrap(), tags, "{}", input);
+            assert_eq!(parsed.task.percent, percent, "{}", input);
+        }
+    }
+
+    #[test]
+    fn keeps_weekday_words_inside_text() {
+        let cases = [
+            "buy sun cream",
+            "wed plans with Tom",
+            "sat exam registration",
+            "call mon ami Pierre",
+        ];
+        for input in cases {
+            let parsed = parse_task_text(input, now());
+            assert!(!parsed.has_date, "{}", input);
+            assert_eq!(parsed.task.text, input);
+        }
+    }
+
+    #[test]
+    fn parses_duration_as_end() {
+        let parsed = parse_task_text("deep work tomorrow 9am for 2h", now());
+        assert_eq!(parsed.task.text, "deep work");
+        assert_eq!(start(&parsed.task), "2026-10-20 09:00");
+        let end = DateTime::parse_from_rfc3339(parsed.task.time.end.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            end.with_timezone(&Local).format("%H:%M").to_string(),
+            "11:00"
+        );
+    }
+}
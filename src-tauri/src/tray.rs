@@ -0,0 +1,259 @@
+// src-tauri/src/tray.rs
+// 托盘菜单：列出今日未完成的任务，任务变化时重建
+// 托盘图标和提示显示今日完成进度
+use crate::app_core::CoreExt;
+use crate::setting::set_remind_later_impl;
+use crate::task_manager::{load_tasks_impl, update_task_impl, Task, TaskParams};
+
+use once_cell::sync::Lazy;
+use std::f64::consts::TAU;
+use std::sync::Mutex;
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder};
+use tauri::tray::TrayIcon;
+use tauri::{AppHandle, Emitter};
+use tokio::time::{sleep, Duration};
+
+pub const TRAY_ID: &str = "tray";
+
+const TASK_PREFIX: &str = "task:";
+const SNOOZE_PREFIX: &str = "snooze:";
+
+// 菜单中展示的任务，点击时据此找到完整的任务
+static TRAY_TASKS: Lazy<Mutex<Vec<Task>>> = Lazy::new(|| Mutex::new(vec![]));
+
+// 今日任务完成进度
+static TRAY_PROGRESS: Lazy<Mutex<Progress>> = Lazy::new(|| Mutex::new(Progress::default()));
+
+#[derive(Default, Clone, Copy, PartialEq)]
+struct Progress {
+    done: usize,
+    total: usize,
+    done_weight: u32,
+    total_weight: u32,
+}
+
+impl Progress {
+    fn from_tasks(tasks: &[Task]) -> Self {
+        let mut progress = Progress::default();
+        for task in tasks {
+            progress.total += 1;
+            progress.total_weight += task.percent;
+            if task.is_done() {
+                progress.done += 1;
+                progress.done_weight += task.percent;
+            }
+        }
+        progress
+    }
+
+    // 按 percent 加权的完成比例，未设置权重时按任务数计算
+    fn ratio(&self) -> f64 {
+        if self.total_weight > 0 {
+            self.done_weight as f64 / self.total_weight as f64
+        } else if self.total > 0 {
+            self.done as f64 / self.total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 构建托盘菜单，tasks 为今日未完成的任务
+pub fn build_tray_menu(app: &AppHandle, tasks: &[Task]) -> tauri::Result<Menu<tauri::Wry>> {
+    let mut builder = MenuBuilder::new(app);
+
+    if tasks.is_empty() {
+        let empty = MenuItemBuilder::with_id("no_tasks", "今日暂无待办")
+            .enabled(false)
+            .build(app)?;
+        builder = builder.item(&empty);
+    }
+    for task in tasks {
+        builder = builder.check(
+            format!("{}{}", TASK_PREFIX, task.id),
+            format!("{}  {}%", task.text, task.percent),
+        );
+    }
+
+    let snooze = SubmenuBuilder::new(app, "稍后提醒")
+        .text(format!("{}1", SNOOZE_PREFIX), "1小时")
+        .text(format!("{}2", SNOOZE_PREFIX), "2小时")
+        .text(format!("{}3", SNOOZE_PREFIX), "3小时")
+        .build()?;
+
+    builder
+        .separator()
+        .item(&snooze)
+        .text("sync", "立即同步")
+        .separator()
+        .text("toggle", "Show/Hide")
+        .text("settings", "Settings")
+        .separator()
+        .text("quit", "Quit")
+        .build()
+}
+
+/// 重新加载今日任务并重建托盘菜单
+pub async fn refresh_tray_menu(app: &AppHandle) {
+    let params = Some(TaskParams::today("0"));
+    let tasks = match load_tasks_impl(&params, app.core()).await {
+        Ok(result) => result.tasks.map(|list| list.tasks).unwrap_or_default(),
+        Err(e) => {
+            log::error!("refresh_tray_menu load_tasks_impl error: {}", e);
+            return;
+        }
+    };
+
+    let progress = Progress::from_tasks(&tasks);
+    let mut pending: Vec<Task> = tasks.into_iter().filter(|task| !task.is_done()).collect();
+    pending.sort_by_key(|task| std::cmp::Reverse(task.percent));
+
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    // 进度变化时才重新绘制图标
+    let changed = *TRAY_PROGRESS.lock().unwrap() != progress;
+    *TRAY_PROGRESS.lock().unwrap() = progress;
+    if changed {
+        update_tray_icon(app, &tray, progress.ratio());
+    }
+    update_tray_tooltip(app, &tray);
+
+    match build_tray_menu(app, &pending) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                log::error!("refresh_tray_menu set_menu error: {:?}", e);
+            }
+        }
+        Err(e) => log::error!("refresh_tray_menu build error: {:?}", e),
+    }
+    *TRAY_TASKS.lock().unwrap() = pending;
+}
+
+pub fn spawn_refresh_tray_menu(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        refresh_tray_menu(&app).await;
+    });
+}
+
+/// 处理动态菜单项
+pub fn handle_tray_menu_event(app: &AppHandle, id: &str) {
+    if let Some(task_id) = id.strip_prefix(TASK_PREFIX) {
+        complete_task(app, task_id);
+    } else if let Some(hours) = id.strip_prefix(SNOOZE_PREFIX) {
+        set_remind_later_impl(hours.parse().unwrap_or(1), app.core());
+    } else if id == "sync" {
+        sync_now(app);
+    }
+}
+
+// 勾选任务即标记为完成
+fn complete_task(app: &AppHandle, task_id: &str) {
+    let task = TRAY_TASKS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|task| task.id == task_id)
+        .cloned();
+    let Some(mut task) = task else {
+        return;
+    };
+    task.mark_done();
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match update_task_impl(&task, app.core()).await {
+            Ok(result) if result.success => {
+                let _ = app.emit("tasks_changed", ());
+            }
+            Ok(_) => log::error!("tray complete_task failed: {}", task.id),
+            Err(e) => log::error!("tray complete_task error: {}", e),
+        }
+    });
+}
+
+/// 定时刷新托盘提示中的下次提醒时间
+pub fn start_tray_status_updater(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                update_tray_tooltip(&app, &tray);
+            }
+            sleep(Duration::from_secs(30)).await;
+        }
+    });
+}
+
+// 例如 "2 of 3 done, next reminder in 25 min"
+fn update_tray_tooltip(app: &AppHandle, tray: &TrayIcon) {
+    let progress = *TRAY_PROGRESS.lock().unwrap();
+    let mut tooltip = if progress.total == 0 {
+        "No tasks today".to_string()
+    } else {
+        format!("{} of {} done", progress.done, progress.total)
+    };
+    if let Some(remaining) = app.core().next_reminder_in() {
+        let minutes = remaining.as_secs().div_ceil(60);
+        tooltip.push_str(&format!(", next reminder in {} min", minutes));
+    }
+    if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+        log::error!("set_tooltip error: {:?}", e);
+    }
+}
+
+fn update_tray_icon(app: &AppHandle, tray: &TrayIcon, ratio: f64) {
+    let Some(base) = app.default_window_icon() else {
+        return;
+    };
+    let icon = render_progress_icon(base, ratio);
+    if let Err(e) = tray.set_icon(Some(icon)) {
+        log::error!("set_icon error: {:?}", e);
+    }
+}
+
+// 在应用图标外圈绘制进度环，从 12 点方向顺时针
+fn render_progress_icon(base: &Image<'_>, ratio: f64) -> Image<'static> {
+    const DONE_COLOR: [u8; 4] = [34, 197, 94, 255];
+    const TRACK_COLOR: [u8; 4] = [209, 213, 219, 255];
+
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+
+    let cx = width as f64 / 2.0;
+    let cy = height as f64 / 2.0;
+    let outer = cx.min(cy);
+    let inner = outer * 0.82;
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f64 + 0.5 - cx;
+            let dy = y as f64 + 0.5 - cy;
+            let r = (dx * dx + dy * dy).sqrt();
+            if r < inner || r > outer {
+                continue;
+            }
+            let angle = (dx.atan2(-dy) + TAU) % TAU;
+            let color = if angle / TAU < ratio {
+                DONE_COLOR
+            } else {
+                TRACK_COLOR
+            };
+            let i = ((y * width + x) * 4) as usize;
+            rgba[i..i + 4].copy_from_slice(&color);
+        }
+    }
+    Image::new_owned(rgba, width, height)
+}
+
+// 立即同步：刷新托盘菜单并通知窗口重新加载
+fn sync_now(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        refresh_tray_menu(&app).await;
+        let _ = app.emit("tasks_changed", ());
+    });
+}
@@ -0,0 +1,337 @@
+// src-tauri/src/webhook.rs
+// 外发 webhook：任务创建、完成和稍后提醒时通知外部系统，例如聊天群、团队看板
+// 请求体默认为 {"event", "timestamp", "task", "minutes"}，也可以在设置中自定义模板，
+// 模板中的 {{task.text}}、{{task.tags}}、{{minutes}} 等占位符会替换为对应的值（按 JSON 字符串转义）
+// 设置了 secret 时带上签名头 X-Webhook-Signature: sha256=<HMAC-SHA256(secret, body) 的十六进制>
+// 失败时重试，每次投递的结果记录在数据目录的 webhook_log.json
+use crate::app_core::{CoreExt, TaskReminderCore};
+use crate::task_manager::Task;
+
+use chrono::Local;
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_http::reqwest;
+
+pub const LOG_FILE: &str = "webhook_log.json";
+
+// 每次尝试前等待的秒数，共尝试 3 次
+const RETRY_DELAYS: [u64; 3] = [0, 5, 30];
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// 一次投递最长的时间：每次重试前的等待加请求超时，另留 5 秒写日志
+const DELIVERY_BUDGET: Duration = {
+    let mut secs = 5;
+    let mut i = 0;
+    while i < RETRY_DELAYS.len() {
+        secs += RETRY_DELAYS[i] + REQUEST_TIMEOUT.as_secs();
+        i += 1;
+    }
+    Duration::from_secs(secs)
+};
+// 日志只保留最近的条数
+const KEEP_RECORDS: usize = 200;
+
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap());
+
+// 进行中的投递数量，命令行退出前等待投递完成
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+static DELIVERY_SEQ: AtomicU64 = AtomicU64::new(0);
+
+// 多个投递线程同时写日志
+static LOG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    TaskCreated,
+    TaskCompleted,
+    ReminderSnoozed, // 稍后提醒，没有对应的任务，带 minutes
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub template: String, // 为空时发送默认的 JSON
+    pub secret: String,   // 为空时不签名
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            enabled: true,
+            url: String::new(),
+            events: vec![WebhookEvent::TaskCreated, WebhookEvent::TaskCompleted],
+            template: String::new(),
+            secret: String::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DeliveryRecord {
+    pub id: String,
+    pub event: WebhookEvent,
+    pub url: String,
+    pub delivered_at: String,
+    pub attempts: u32,
+    pub success: bool,
+    pub status: Option<u16>, // 最后一次的 HTTP 状态码
+    pub error: Option<String>,
+}
+
+#[tauri::command]
+pub fn load_webhook_log(app: AppHandle) -> Result<Vec<DeliveryRecord>, String> {
+    load_records(&app.core().data_file(LOG_FILE)?)
+}
+
+/// 在后台发送事件到所有订阅了该事件的 webhook
+pub fn fire(
+    core: &TaskReminderCore,
+    event: WebhookEvent,
+    task: Option<&Task>,
+    minutes: Option<u64>,
+) {
+    let hooks: Vec<WebhookConfig> = match core.load_setting() {
+        Ok(setting) => setting
+            .webhooks
+            .into_iter()
+            .filter(|hook| hook.enabled && !hook.url.trim().is_empty())
+            .filter(|hook| hook.events.contains(&event))
+            .collect(),
+        Err(e) => {
+            log::error!("load webhooks error: {}", e);
+            return;
+        }
+    };
+    if hooks.is_empty() {
+        return;
+    }
+    let log_file = match core.data_file(LOG_FILE) {
+        Ok(log_file) => log_file,
+        Err(e) => {
+            log::error!("webhook log file error: {}", e);
+            return;
+        }
+    };
+
+    let payload = json!({
+        "event": event,
+        "timestamp": Local::now().to_rfc3339(),
+        "task": task,
+        "minutes": minutes,
+    });
+    for hook in hooks {
+        let body = if hook.template.trim().is_empty() {
+            payload.to_string()
+        } else {
+            render(&hook.template, &payload)
+        };
+        let log_file = log_file.clone();
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        std::thread::spawn(move || {
+            deliver(&hook, event, &body, &log_file);
+            IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/// 等待进行中的投递（包括重试）完成，用于命令行退出前，最多等待一次投递的最长时间
+pub fn wait_for_deliveries() {
+    let start = Instant::now();
+    while IN_FLIGHT.load(Ordering::SeqCst) > 0 && start.elapsed() < DELIVERY_BUDGET {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn deliver(hook: &WebhookConfig, event: WebhookEvent, body: &str, log_file: &Path) {
+    let id = format!(
+        "{}-{}",
+        Local::now().timestamp_millis(),
+        DELIVERY_SEQ.fetch_add(1, Ordering::SeqCst)
+    );
+    let mut record = DeliveryRecord {
+        id,
+        event,
+        url: hook.url.clone(),
+        delivered_at: String::new(),
+        attempts: 0,
+        success: false,
+        status: None,
+        error: None,
+    };
+
+    for delay in RETRY_DELAYS {
+        std::thread::sleep(Duration::from_secs(delay));
+        record.attempts += 1;
+        let result = tauri::async_runtime::block_on(send(hook, event, &record.id, body));
+        record.delivered_at = Local::now().to_rfc3339();
+        match result {
+            Ok(status) => {
+                record.status = Some(status);
+                record.success = (200..300).contains(&status);
+                record.error = None;
+                // 4xx 说明请求本身有问题，重试也不会成功，超时和限流除外
+                let retry = status >= 500 || status == 408 || status == 429;
+                if record.success || !retry {
+                    break;
+                }
+            }
+            Err(e) => {
+                record.status = None;
+                record.error = Some(e);
+            }
+        }
+    }
+
+    if record.success {
+        log::info!("webhook {:?} delivered to {}", event, hook.url);
+    } else {
+        log::warn!(
+            "webhook {:?} to {} failed after {} attempts: {:?} {:?}",
+            event,
+            hook.url,
+            record.attempts,
+            record.status,
+            record.error
+        );
+    }
+    if let Err(e) = append_record(record, log_file) {
+        log::error!("save webhook log error: {}", e);
+    }
+}
+
+async fn send(
+    hook: &WebhookConfig,
+    event: WebhookEvent,
+    delivery_id: &str,
+    body: &str,
+) -> Result<u16, String> {
+    let event_name = serde_json::to_value(event)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client
+        .post(hook.url.trim())
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "task-reminder")
+        .header("X-Webhook-Event", event_name)
+        .header("X-Webhook-Delivery", delivery_id)
+        .body(body.to_string());
+    if !hook.secret.is_empty() {
+        request = request.header(
+            "X-Webhook-Signature",
+            format!("sha256={}", sign(&hook.secret, body)),
+        );
+    }
+
+    let res = request.send().await.map_err(|e| e.to_string())?;
+    Ok(res.status().as_u16())
+}
+
+fn sign(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("any key length");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// 替换模板中的 {{path}}，例如 {{task.time.start}}，数组按逗号连接，不存在的值替换为空
+fn render(template: &str, payload: &Value) -> String {
+    PLACEHOLDER_RE
+        .replace_all(template, |caps: &Captures| {
+            let pointer = format!("/{}", caps[1].replace('.', "/"));
+            let text = match payload.pointer(&pointer) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .map(|item| match item {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                Some(other) => other.to_string(),
+            };
+            // 模板通常是 JSON，值按字符串内容转义，去掉两边的引号
+            let escaped = serde_json::to_string(&text).unwrap_or_default();
+            escaped[1..escaped.len() - 1].to_string()
+        })
+        .to_string()
+}
+
+fn load_records(log_file: &Path) -> Result<Vec<DeliveryRecord>, String> {
+    if !log_file.exists() {
+        return Ok(vec![]);
+    }
+    let json = std::fs::read_to_string(log_file).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| format!("解析 {} 失败: {}", LOG_FILE, e))
+}
+
+fn append_record(record: DeliveryRecord, log_file: &Path) -> Result<(), String> {
+    let _lock = LOG_LOCK.lock().unwrap();
+    let mut records = load_records(log_file).unwrap_or_default();
+    records.push(record);
+    let overflow = records.len().saturating_sub(KEEP_RECORDS);
+    records.drain(..overflow);
+
+    let json = serde_json::to_string_pretty(&records).map_err(|e| e.to_string())?;
+    std::fs::write(log_file, json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_notion::test_task;
+
+    #[test]
+    fn render_escapes_values_as_json_strings() {
+        let mut task = test_task("写 \"周报\"\n第二行 C:\\docs", "完成", "2026-10-19");
+        task.tags = Some(vec!["工作".to_string(), "a\"b".to_string()]);
+        let payload = json!({
+            "event": WebhookEvent::TaskCompleted,
+            "task": task,
+            "minutes": null,
+        });
+        let template = r#"{"text": "已完成 {{ task.text }}", "tags": "{{task.tags}}", "percent": {{task.percent}}, "minutes": "{{minutes}}", "missing": "{{task.owner}}"}"#;
+
+        let rendered = render(template, &payload);
+        let value: Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["text"], "已完成 写 \"周报\"\n第二行 C:\\docs");
+        assert_eq!(value["tags"], "工作, a\"b");
+        assert_eq!(value["percent"], 0);
+        assert_eq!(value["minutes"], "");
+        assert_eq!(value["missing"], "");
+    }
+
+    #[test]
+    fn sign_matches_hmac_sha256() {
+        // RFC 4231 测试用例 2
+        assert_eq!(
+            sign("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn cli_waits_for_all_retries() {
+        let retries: u64 = RETRY_DELAYS.iter().sum();
+        let requests = REQUEST_TIMEOUT * RETRY_DELAYS.len() as u32;
+        assert!(DELIVERY_BUDGET > Duration::from_secs(retries) + requests);
+    }
+}
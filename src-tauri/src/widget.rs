@@ -0,0 +1,205 @@
+// src-tauri/src/widget.rs
+// 小组件模式：置顶、无边框的小窗口，只显示今日最重要的任务
+use crate::app_core::CoreExt;
+use crate::window_manager::show_popup;
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, PhysicalSize, UserAttentionType, WebviewWindow, Window,
+};
+
+pub const WIDGET_LABEL: &str = "widget";
+
+// 拖动和缩放时连续触发事件，停止一段时间后才写文件
+const SAVE_GEOMETRY_DELAY: Duration = Duration::from_millis(500);
+// 每次移动或缩放加一，延迟结束时没有新的变化才保存
+static GEOMETRY_SEQ: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowMode {
+    #[default]
+    Full,
+    Widget,
+}
+
+/// 小组件状态，保存在 widget.json
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WidgetState {
+    pub mode: WindowMode,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for WidgetState {
+    fn default() -> Self {
+        WidgetState {
+            mode: WindowMode::Full,
+            x: None,
+            y: None,
+            width: 260,
+            height: 220,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ModeResult {
+    success: bool,
+    mode: WindowMode,
+}
+
+#[tauri::command]
+pub fn set_window_mode(mode: WindowMode, app: AppHandle) -> Result<ModeResult, String> {
+    set_window_mode_impl(mode, &app)
+}
+
+#[tauri::command]
+pub fn toggle_window_mode(app: AppHandle) -> Result<ModeResult, String> {
+    let mode = match load_widget_state(&app).mode {
+        WindowMode::Full => WindowMode::Widget,
+        WindowMode::Widget => WindowMode::Full,
+    };
+    set_window_mode_impl(mode, &app)
+}
+
+#[tauri::command]
+pub fn load_window_mode(app: AppHandle) -> Result<WindowMode, String> {
+    Ok(load_widget_state(&app).mode)
+}
+
+pub fn set_window_mode_impl(mode: WindowMode, app: &AppHandle) -> Result<ModeResult, String> {
+    log::info!("set_window_mode: {:?}", mode);
+    let main = app.get_webview_window("main");
+
+    match mode {
+        WindowMode::Widget => {
+            let widget = open_widget_window(app)?;
+            if let Some(main) = main {
+                main.hide().map_err(|e| e.to_string())?;
+            }
+            widget.show().map_err(|e| e.to_string())?;
+        }
+        WindowMode::Full => {
+            if let Some(widget) = app.get_webview_window(WIDGET_LABEL) {
+                widget.hide().map_err(|e| e.to_string())?;
+            }
+            if let Some(main) = main {
+                show_popup(app, &main);
+            }
+        }
+    }
+
+    let mut state = load_widget_state(app);
+    state.mode = mode;
+    save_widget_state(&state, app)?;
+
+    Ok(ModeResult {
+        success: true,
+        mode,
+    })
+}
+
+/// 启动时恢复上次的窗口模式
+pub fn restore_window_mode(app: &AppHandle) {
+    if load_widget_state(app).mode == WindowMode::Widget {
+        if let Err(e) = set_window_mode_impl(WindowMode::Widget, app) {
+            log::error!("restore_window_mode error: {}", e);
+        }
+    }
+}
+
+/// 当前为小组件模式时返回小组件窗口
+pub fn active_widget(app: &AppHandle) -> Option<WebviewWindow> {
+    if load_widget_state(app).mode != WindowMode::Widget {
+        return None;
+    }
+    app.get_webview_window(WIDGET_LABEL)
+}
+
+/// 小组件模式下提醒时只闪烁小组件，不打断当前工作
+pub fn remind_widget(widget: &WebviewWindow) -> Result<(), String> {
+    widget.show().map_err(|e| e.to_string())?;
+    widget
+        .request_user_attention(Some(UserAttentionType::Informational))
+        .map_err(|e| e.to_string())
+}
+
+fn open_widget_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+    if let Some(widget) = app.get_webview_window(WIDGET_LABEL) {
+        return Ok(widget);
+    }
+
+    log::info!("create widget window");
+    let state = load_widget_state(app);
+    let widget = tauri::WebviewWindowBuilder::new(
+        app,
+        WIDGET_LABEL,
+        tauri::WebviewUrl::App("#/widget".into()),
+    )
+    .title("今日任务")
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(true)
+    .visible(false)
+    .build()
+    .map_err(|e| e.to_string())?;
+
+    // 保存的是物理像素，创建后再设置，避免不同缩放比例下尺寸不一致
+    widget
+        .set_size(PhysicalSize::new(state.width, state.height))
+        .map_err(|e| e.to_string())?;
+    if let (Some(x), Some(y)) = (state.x, state.y) {
+        widget
+            .set_position(PhysicalPosition::new(x, y))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(widget)
+}
+
+/// 小组件移动或缩放后保存位置和大小
+pub fn save_widget_geometry(window: &Window) {
+    let seq = GEOMETRY_SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_GEOMETRY_DELAY).await;
+        if GEOMETRY_SEQ.load(Ordering::SeqCst) == seq {
+            write_widget_geometry(&window);
+        }
+    });
+}
+
+fn write_widget_geometry(window: &Window) {
+    let app = window.app_handle();
+    let mut state = load_widget_state(app);
+    if let Ok(position) = window.outer_position() {
+        state.x = Some(position.x);
+        state.y = Some(position.y);
+    }
+    if let Ok(size) = window.inner_size() {
+        state.width = size.width;
+        state.height = size.height;
+    }
+    if let Err(e) = save_widget_state(&state, app) {
+        log::error!("save_widget_geometry error: {}", e);
+    }
+}
+
+fn load_widget_state(app: &AppHandle) -> WidgetState {
+    std::fs::read_to_string(app.core().config_dir().join("widget.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_widget_state(state: &WidgetState, app: &AppHandle) -> Result<(), String> {
+    let config_dir = app.core().config_dir();
+    std::fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    std::fs::write(config_dir.join("widget.json"), json).map_err(|e| e.to_string())
+}
@@ -1,13 +1,35 @@
 // src-tauri/src/window_manager.rs
-use crate::setting::get_remind_later_impl;
-use crate::task_manager::load_tasks_impl;
+use crate::app_core::CoreExt;
+use crate::escalation;
+use crate::hooks::{self, HookEvent};
+use crate::idle::ResumeDetector;
+use crate::reminder::{dispatch, Reminder};
+use crate::reminder_log::{record_fired, record_outcome, Outcome};
+use crate::setting::{get_remind_later_impl, load_setting_impl, setting_generation};
+use crate::task_manager::{load_tasks_impl, Task};
 
 // use std::thread;
 // use std::time::{Duration, Instant};
-use tauri::{AppHandle, Manager};
-use tokio::time::{sleep, Duration};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Monitor, PhysicalPosition, WebviewWindow, Window};
+use tokio::time::{sleep, Duration, Instant};
 
-// use tauri::{LogicalPosition, WebviewWindow};
+// 拖动窗口时连续触发 Moved，停止移动一段时间后才写文件
+const SAVE_POSITION_DELAY: Duration = Duration::from_millis(500);
+// 每次移动加一，延迟结束时没有新的移动才保存
+static POSITION_SEQ: AtomicU64 = AtomicU64::new(0);
+// place_window 移动窗口的时间，随后的 Moved 不是用户拖动，不记录
+static PLACED_AT: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+// 记录下次提醒的时间点，未在计时（窗口可见、空闲暂停等）时为 None
+fn set_next_reminder_at(app_handle: &AppHandle, next: Option<Instant>) {
+    app_handle
+        .core()
+        .set_next_reminder_at(next.map(Instant::into_std));
+}
 
 /// 启动周期性弹窗任务
 pub fn start_periodic_popup(app_handle: AppHandle, window_label: &str) {
@@ -17,39 +39,160 @@ pub fn start_periodic_popup(app_handle: AppHandle, window_label: &str) {
             // 只有窗口不可见时才开始计时
             if let Some(window) = app_handle.get_webview_window(&label) {
                 // [1]如果窗口可见，等待直到窗口不可见
+                let mut was_visible = false;
+                set_next_reminder_at(&app_handle, None);
                 while window.is_visible().unwrap_or(true) {
+                    was_visible = true;
                     sleep(Duration::from_secs(1)).await;
                 }
+                // 窗口被关闭，若有待响应的提醒则记为已关闭
+                if was_visible {
+                    record_outcome(Outcome::Dismissed, None, app_handle.core());
+                    escalation::mark_hidden(app_handle.core());
+                }
 
-                let tasks = load_tasks_impl(&None, &app_handle).await.unwrap();
-                if tasks.tasks.as_ref().unwrap().tasks.len() == 0 {
+                // 未登录或加载失败时跳过这一轮，稍后再试
+                let tasks = match load_tasks_impl(&None, app_handle.core()).await {
+                    Ok(result) if result.success => {
+                        result.tasks.map(|list| list.tasks).unwrap_or_default()
+                    }
+                    Ok(result) => {
+                        log::info!("skip reminder, load tasks failed: {:?}", result.status);
+                        sleep(Duration::from_secs(60)).await;
+                        continue;
+                    }
+                    Err(e) => {
+                        log::error!("skip reminder, load tasks error: {}", e);
+                        sleep(Duration::from_secs(60)).await;
+                        continue;
+                    }
+                };
+                if tasks.is_empty() {
                     println!("no tasks");
                     sleep(Duration::from_secs(60)).await;
                     continue;
                 }
 
-                // [2]记录不可见开始时间，等待到interval
-                let start = tokio::time::Instant::now();
+                // 未完成的任务，已按 percent 降序
+                let pending: Vec<_> = tasks.into_iter().filter(|task| !task.is_done()).collect();
+
+                // 最重要的任务一直未开始时升级提醒
+                let level = escalation::observe(pending.first(), app_handle.core());
+                let mut generation = setting_generation();
+                let setting = load_setting_impl(&app_handle);
+                let mut escalation_setting = setting
+                    .as_ref()
+                    .map(|setting| setting.escalation.clone())
+                    .unwrap_or_default();
+                let mut idle_setting = setting
+                    .as_ref()
+                    .map(|setting| setting.idle.clone())
+                    .unwrap_or_default();
+
+                // [2]开始计时，用户空闲期间不计入，等待到interval
+                let mut counted = Duration::ZERO;
+                let mut last_tick = Instant::now();
+                let mut last_idle_check = last_tick;
+                let mut idle_since: Option<Instant> = None;
+                let mut clock = ResumeDetector::new();
+                let mut away: Option<Duration> = None;
 
-                let reminder_minutes = get_remind_later_impl(&app_handle);
-                let interval = reminder_minutes * 60;
+                let reminder_minutes =
+                    escalation_setting.interval_minutes(get_remind_later_impl(&app_handle), level);
+                let mut interval = reminder_minutes * 60;
                 println!("interval: {}", interval);
-                while start.elapsed().as_secs() < interval {
+                while counted.as_secs() < interval {
                     // [3]如果窗口在计时期间变为可见，重置计时
                     if window.is_visible().unwrap_or(true) {
                         break;
                     }
+
+                    // 配置修改后立即按新的提醒频率计时，已计的时间保留
+                    if setting_generation() != generation {
+                        generation = setting_generation();
+                        if let Ok(setting) = load_setting_impl(&app_handle) {
+                            escalation_setting = setting.escalation;
+                            idle_setting = setting.idle;
+                        }
+                        interval = escalation_setting
+                            .interval_minutes(get_remind_later_impl(&app_handle), level)
+                            * 60;
+                        log::info!("setting changed, interval: {}", interval);
+                    }
+
+                    // 系统休眠后唤醒，重新计时
+                    if let Some(gap) = clock.check() {
+                        log::info!("resumed from sleep after {}s", gap.as_secs());
+                        away = Some(gap);
+                        break;
+                    }
+
+                    // 空闲检测需要调用外部命令，每 10 秒检查一次
+                    let now = Instant::now();
+                    if now - last_idle_check >= Duration::from_secs(10) {
+                        last_idle_check = now;
+                        match (idle_setting.is_idle(), idle_since) {
+                            (true, None) => {
+                                log::info!("user idle, pause reminder");
+                                idle_since = Some(now);
+                            }
+                            (false, Some(since)) => {
+                                idle_since = None;
+                                // 判定空闲前的无操作时间也不计入，重新计时
+                                counted = Duration::ZERO;
+                                let gap = now - since
+                                    + Duration::from_secs(idle_setting.idle_minutes * 60);
+                                log::info!("user back after {}s", gap.as_secs());
+                                if idle_setting.should_welcome(gap) {
+                                    away = Some(gap);
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    if idle_since.is_none() {
+                        counted += now - last_tick;
+                        let remaining = Duration::from_secs(interval).saturating_sub(counted);
+                        set_next_reminder_at(&app_handle, Some(now + remaining));
+                    } else {
+                        set_next_reminder_at(&app_handle, None);
+                    }
+                    last_tick = now;
+
                     sleep(Duration::from_millis(200)).await;
                 }
 
+                // 离开较久后回来，展示今日任务并重新计时
+                if let Some(gap) = away {
+                    if idle_setting.should_welcome(gap) {
+                        welcome_back(&app_handle, &window, &pending);
+                    }
+                    continue;
+                }
+
                 // [4]如果窗口在计时期间变为可见，跳过本次弹窗
                 if window.is_visible().unwrap_or(true) {
                     continue;
                 }
 
-                // [5]这里窗口不可见，弹窗
-                let _ = window.show();
-                let _ = window.set_focus();
+                // [5]这里窗口不可见，通过配置的渠道发出提醒
+                let _ = window.set_always_on_top(escalation_setting.keep_on_top(level));
+                let reminder = Reminder::new(&label, pending, level);
+                record_fired(&reminder.fired_at, app_handle.core());
+                dispatch(&app_handle, &reminder);
+                hooks::fire(
+                    app_handle.core(),
+                    HookEvent::ReminderFired,
+                    reminder.tasks.first(),
+                    serde_json::json!({
+                        "fired_at": reminder.fired_at,
+                        "text": reminder.summary(),
+                        "tasks": reminder.tasks,
+                    }),
+                );
+                let popup = window.is_visible().unwrap_or(false);
+                escalation::mark_fired(popup, app_handle.core());
             } else {
                 // 没找到窗口，稍后重试
                 sleep(Duration::from_secs(1)).await;
@@ -57,40 +200,200 @@ pub fn start_periodic_popup(app_handle: AppHandle, window_label: &str) {
         }
     });
 }
-/*
-/// 将窗口移动到右上角
-pub fn move_to_top_right(window: &WebviewWindow) -> tauri::Result<()> {
-    let monitor = window.current_monitor()?.unwrap();
-    let screen_size = monitor.size();
-    let screen_width = screen_size.width;
-    let window_size = window.outer_size()?;
-    let window_width = window_size.width;
-
-    let x_right = (screen_width - window_width) as f64;
-    let y_top = 0.0;
-
-    window.set_position(LogicalPosition {
-        x: x_right,
-        y: y_top,
-    })
-}
-
-/// 将窗口移动到右下角
-pub fn move_to_bottom_right(window: &WebviewWindow) -> tauri::Result<()> {
-    let monitor = window.current_monitor()?.unwrap();
-    let screen_size = monitor.size();
-    let screen_width = screen_size.width;
-    let screen_height = screen_size.height;
-    let window_size = window.outer_size()?;
-    let window_width = window_size.width;
-    let window_height = window_size.height;
-
-    let x_right = (screen_width - window_width) as f64;
-    let y_bottom = (screen_height - window_height) as f64;
-
-    window.set_position(LogicalPosition {
-        x: x_right,
-        y: y_bottom,
-    })
-}
-*/
+
+// 欢迎回来：显示窗口并通知前端切换到今日任务
+fn welcome_back(app_handle: &AppHandle, window: &WebviewWindow, pending: &[Task]) {
+    let priorities: Vec<Task> = pending.iter().take(3).cloned().collect();
+    show_popup(app_handle, window);
+    if let Err(e) = app_handle.emit("welcome_back", priorities) {
+        log::error!("emit welcome_back error: {:?}", e);
+    }
+}
+
+/// 弹窗位置
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowAnchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+    #[default]
+    Last, // 上次关闭时的位置
+}
+
+/// 上次窗口位置，保存在 window_position.json
+#[derive(Serialize, Deserialize)]
+struct SavedPosition {
+    x: i32,
+    y: i32,
+    monitor: Option<String>,
+}
+
+// 窗口与屏幕边缘的距离（逻辑像素）
+const SCREEN_MARGIN: f64 = 8.0;
+
+/// 显示或隐藏主窗口
+pub fn toggle_main_window(app_handle: &AppHandle) {
+    let Some(win) = app_handle.get_webview_window("main") else {
+        return;
+    };
+    if win.is_visible().unwrap_or(false) {
+        let _ = win.hide();
+    } else {
+        show_popup(app_handle, &win);
+    }
+}
+
+/// 按配置的位置显示并聚焦窗口
+pub fn show_popup(app_handle: &AppHandle, window: &WebviewWindow) {
+    if let Err(e) = place_window(app_handle, window) {
+        log::error!("place_window error: {:?}", e);
+    }
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+/// 将窗口移动到配置的位置
+pub fn place_window(app_handle: &AppHandle, window: &WebviewWindow) -> tauri::Result<()> {
+    let anchor = load_setting_impl(app_handle)
+        .map(|setting| setting.anchor)
+        .unwrap_or_default();
+
+    if anchor == WindowAnchor::Last {
+        match load_last_position(app_handle) {
+            // 保存的位置仍在某个屏幕内
+            Some(saved) if position_visible(app_handle, &saved)? => {
+                return move_window(window, PhysicalPosition::new(saved.x, saved.y));
+            }
+            // 从未保存过位置，保持窗口当前位置
+            None => return Ok(()),
+            // 保存位置所在的屏幕已经不存在，退回到右上角
+            Some(_) => return move_to_anchor(app_handle, window, WindowAnchor::TopRight),
+        }
+    }
+    move_to_anchor(app_handle, window, anchor)
+}
+
+/// 在鼠标所在的屏幕上，按锚点计算窗口位置
+fn move_to_anchor(
+    app_handle: &AppHandle,
+    window: &WebviewWindow,
+    anchor: WindowAnchor,
+) -> tauri::Result<()> {
+    let Some(monitor) = cursor_monitor(app_handle, window)? else {
+        return Ok(());
+    };
+
+    // 目标屏幕缩放比例可能不同，按逻辑尺寸换算窗口在目标屏幕上的物理尺寸
+    let window_size = window
+        .outer_size()?
+        .to_logical::<f64>(window.scale_factor()?);
+    let scale = monitor.scale_factor();
+    let width = (window_size.width * scale) as i32;
+    let height = (window_size.height * scale) as i32;
+    let margin = (SCREEN_MARGIN * scale) as i32;
+
+    let area = monitor.work_area();
+    let left = area.position.x + margin;
+    let top = area.position.y + margin;
+    let right = area.position.x + area.size.width as i32 - width - margin;
+    let bottom = area.position.y + area.size.height as i32 - height - margin;
+
+    let (x, y) = match anchor {
+        WindowAnchor::TopLeft => (left, top),
+        WindowAnchor::TopRight | WindowAnchor::Last => (right, top),
+        WindowAnchor::BottomLeft => (left, bottom),
+        WindowAnchor::BottomRight => (right, bottom),
+        WindowAnchor::Center => (
+            area.position.x + (area.size.width as i32 - width) / 2,
+            area.position.y + (area.size.height as i32 - height) / 2,
+        ),
+    };
+    // 窗口比屏幕大时至少保证左上角可见
+    move_window(window, PhysicalPosition::new(x.max(left), y.max(top)))
+}
+
+fn move_window(window: &WebviewWindow, position: PhysicalPosition<i32>) -> tauri::Result<()> {
+    *PLACED_AT.lock().unwrap() = Some(Instant::now());
+    window.set_position(position)
+}
+
+// 鼠标所在的屏幕，获取不到时依次退回到窗口所在屏幕和主屏幕
+fn cursor_monitor(
+    app_handle: &AppHandle,
+    window: &WebviewWindow,
+) -> tauri::Result<Option<Monitor>> {
+    if let Ok(cursor) = app_handle.cursor_position() {
+        if let Some(monitor) = app_handle.monitor_from_point(cursor.x, cursor.y)? {
+            return Ok(Some(monitor));
+        }
+    }
+    if let Some(monitor) = window.current_monitor()? {
+        return Ok(Some(monitor));
+    }
+    app_handle.primary_monitor()
+}
+
+// 保存的位置是否仍在现有屏幕内
+fn position_visible(app_handle: &AppHandle, saved: &SavedPosition) -> tauri::Result<bool> {
+    let monitors = app_handle.available_monitors()?;
+    Ok(monitors.iter().any(|monitor| {
+        let same_name = match &saved.monitor {
+            Some(name) => monitor.name() == Some(name),
+            None => true,
+        };
+        let area = monitor.work_area();
+        same_name
+            && saved.x >= area.position.x
+            && saved.y >= area.position.y
+            && saved.x < area.position.x + area.size.width as i32
+            && saved.y < area.position.y + area.size.height as i32
+    }))
+}
+
+fn position_file(app_handle: &AppHandle) -> Option<std::path::PathBuf> {
+    Some(app_handle.core().config_dir().join("window_position.json"))
+}
+
+fn load_last_position(app_handle: &AppHandle) -> Option<SavedPosition> {
+    let json = std::fs::read_to_string(position_file(app_handle)?).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// 用户拖动窗口后记录位置，供 WindowAnchor::Last 使用
+pub fn save_last_position(window: &Window, position: &PhysicalPosition<i32>) {
+    let placed_at = *PLACED_AT.lock().unwrap();
+    if placed_at.is_some_and(|at| at.elapsed() < SAVE_POSITION_DELAY) {
+        return;
+    }
+    let seq = POSITION_SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+    let window = window.clone();
+    let position = *position;
+    tauri::async_runtime::spawn(async move {
+        sleep(SAVE_POSITION_DELAY).await;
+        if POSITION_SEQ.load(Ordering::SeqCst) == seq {
+            write_last_position(&window, &position);
+        }
+    });
+}
+
+fn write_last_position(window: &Window, position: &PhysicalPosition<i32>) {
+    let Some(file_path) = position_file(window.app_handle()) else {
+        return;
+    };
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|monitor| monitor.name().cloned());
+    let saved = SavedPosition {
+        x: position.x,
+        y: position.y,
+        monitor,
+    };
+    if let Ok(json) = serde_json::to_string(&saved) {
+        let _ = std::fs::write(file_path, json);
+    }
+}
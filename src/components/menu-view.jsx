@@ -5,7 +5,7 @@ import {
     ContextMenuItem,
     ContextMenuTrigger,
 } from "@/components/ui/context-menu"
-import { RefreshCw, Settings, Tag } from "lucide-react"
+import { RefreshCw, Settings, Tag, PictureInPicture2 } from "lucide-react"
 import { toast } from "sonner";
 import { invoke } from "@tauri-apps/api/core";
 import NotionTag from "@/components/notion-tag";
@@ -27,6 +27,14 @@ export default function MenuView({ children, loadTasks }) {
         }
     }
 
+    const handleWidgetMode = async () => {
+        try {
+            await invoke("set_window_mode", { mode: "widget" })
+        } catch (err) {
+            toast.error(err.toString())
+        }
+    }
+
     const forceRefresh = async () => {
         try {
             window.location.reload();
@@ -46,6 +54,9 @@ export default function MenuView({ children, loadTasks }) {
                     <ContextMenuItem onClick={() => setOpen(true)}>
                         <Tag />管理标签
                     </ContextMenuItem>
+                    <ContextMenuItem onClick={handleWidgetMode}>
+                        <PictureInPicture2 />小组件模式
+                    </ContextMenuItem>
                     <ContextMenuItem onClick={handleOpenSettings}>
                         <Settings />设置
                     </ContextMenuItem>
@@ -183,7 +183,7 @@ export const TextareaCommand = forwardRef(({ value, tags, onChange, disabled, ..
     return (
         <div className="w-full flex flex-col gap-1">
             <textarea
-                placeholder="输入任务...   使用#添加标签"
+                placeholder="输入任务...   使用#添加标签，/help 查看命令"
                 rows={1}
                 className="focus:outline-none focus:ring-0 resize-none overflow-hidden" type="textarea"
                 ref={textareaRef} value={text}
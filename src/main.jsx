@@ -8,6 +8,8 @@ import NotionProvider from './context/NotionProvider'
 // import TagProvider from './context/TagProvider'
 import TaskList from './tasklist'
 import Settings from './settings'
+import Widget from './widget'
+import QuickAdd from './quick-add'
 import { Toaster } from "@/components/ui/sonner"
 
 createRoot(document.getElementById('root')).render(
@@ -19,6 +21,8 @@ createRoot(document.getElementById('root')).render(
         <Routes>
           <Route path="/main" element={<TaskList />} />
           <Route path="/settings" element={<Settings />} />
+          <Route path="/widget" element={<Widget />} />
+          <Route path="/quick-add" element={<QuickAdd />} />
         </Routes>
       </HashRouter>
       <Toaster />
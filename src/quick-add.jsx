@@ -0,0 +1,92 @@
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { toast } from "sonner";
+
+import { Input } from "@/components/ui/input";
+
+// 快速添加：全局快捷键唤出，回车保存
+// 支持自然语言，例如 "call bank tomorrow 3pm #finance 30%"、"明天下午三点开会 #工作"
+// 通过 task-reminder://add 链接打开时填入链接中的任务，回车确认后才保存
+export default function QuickAdd() {
+    const [text, setText] = useState("")
+    const [saving, setSaving] = useState(false)
+    const inputRef = useRef(null)
+
+    useEffect(() => {
+        // 失去焦点（窗口随之隐藏）时清空，唤出时填入链接中的任务（如果有）并聚焦输入框
+        const takePrefill = () => {
+            invoke("take_quick_add_text").then(prefill => {
+                if (prefill) {
+                    setText(prefill)
+                }
+            })
+            inputRef.current?.focus()
+        }
+        const unlistenFocus = getCurrentWindow().onFocusChanged(({ payload: focused }) => {
+            if (focused) {
+                takePrefill()
+            } else {
+                setText("")
+            }
+        })
+        const unlistenPrefill = listen("quick_add_prefill", takePrefill)
+        takePrefill()
+        return () => {
+            unlistenFocus.then(fn => fn())
+            unlistenPrefill.then(fn => fn())
+        }
+    }, [])
+
+    const hide = async () => {
+        setText("")
+        await invoke("hide_quick_add_window")
+    }
+
+    const handleSave = async () => {
+        if (!text.trim() || saving) {
+            return
+        }
+        setSaving(true)
+        try {
+            const { task } = await invoke("parse_task", { text: text.trim() })
+            if (!task.text) {
+                toast.error("任务内容不能为空")
+                return
+            }
+            const res = await invoke("add_task", { task })
+            if (res.success) {
+                await hide()
+            } else {
+                toast.error(`创建失败: ${res.error}`)
+            }
+        } catch (err) {
+            toast.error(`操作失败: ${err}`)
+        } finally {
+            setSaving(false)
+        }
+    }
+
+    const handleKeyDown = (e) => {
+        if (e.key === "Enter" && !e.nativeEvent.isComposing) {
+            e.preventDefault()
+            handleSave()
+        } else if (e.key === "Escape") {
+            hide()
+        }
+    }
+
+    return (
+        <div className="h-screen flex items-center px-3 bg-background" data-tauri-drag-region>
+            <Input
+                ref={inputRef}
+                value={text}
+                disabled={saving}
+                placeholder="明天下午3点 写周报 #工作 30%"
+                onChange={(e) => setText(e.target.value)}
+                onKeyDown={handleKeyDown}
+            />
+        </div>
+    )
+}
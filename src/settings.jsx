@@ -5,6 +5,7 @@ import { Switch } from "@/components/ui/switch"
 import { Input } from "@/components/ui/input"
 import { Button } from "@/components/ui/button"
 import { Label } from "@/components/ui/label"
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select"
 import { Tooltip, TooltipContent, TooltipTrigger } from "@/components/ui/tooltip";
 
 import { Bell } from "lucide-react";
@@ -14,60 +15,263 @@ import NotionPage from "@/components/notion-page"
 
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { useNotionContext } from "@/context/NotionContext";
 import { toast } from "sonner";
 
 
-export default function Settings() {
+// webhook 可订阅的事件
+const webhookEvents = [
+  { label: "创建任务", value: "task_created" },
+  { label: "完成任务", value: "task_completed" },
+  { label: "稍后提醒", value: "reminder_snoozed" },
+]
 
-  const { state, authInfo, logout, version, latestVersion, updateVersion } = useNotionContext();
+// 可以执行本地命令的事件
+const hookEvents = [
+  { label: "完成任务", value: "task_completed" },
+  { label: "发出提醒", value: "reminder_fired" },
+  { label: "新的一天", value: "day_started" },
+]
+
+const exportFormats = [
+  { label: "Markdown", value: "markdown" },
+  { label: "CSV", value: "csv" },
+  { label: "JSON", value: "json" },
+]
+
+const importFormats = [
+  { label: "CSV", value: "csv" },
+  { label: "todo.txt", value: "todo_txt" },
+  { label: "JSON", value: "json" },
+]
+
+// CSV 中各字段对应的列名，默认与导出的列名相同
+const csvMappingFields = [
+  { label: "内容", key: "text" },
+  { label: "日期", key: "date" },
+  { label: "状态", key: "status" },
+  { label: "权重", key: "percent" },
+  { label: "标签", key: "tags" },
+]
+
+const exportStatusOptions = [
+  { label: "全部", value: "0" },
+  { label: "未完成", value: "1" },
+  { label: "已完成", value: "2" },
+]
+
+// 本周一到今天，用于导出的默认日期范围
+function thisWeek() {
+  const format = (d) => `${d.getFullYear()}-${String(d.getMonth() + 1).padStart(2, "0")}-${String(d.getDate()).padStart(2, "0")}`
+  const today = new Date()
+  const monday = new Date(today)
+  monday.setDate(today.getDate() - (today.getDay() + 6) % 7)
+  return { start: format(monday), end: format(today) }
+}
+
+const anchorOptions = [
+  { label: "上次位置", value: "last" },
+  { label: "右上角", value: "top_right" },
+  { label: "右下角", value: "bottom_right" },
+  { label: "左上角", value: "top_left" },
+  { label: "左下角", value: "bottom_left" },
+  { label: "居中", value: "center" },
+]
 
-  const [isOn, setIsOn] = useState(false)
+export default function Settings() {
 
+  const { state, authInfo, logout, version, latestVersion, updateVersion } = useNotionContext();
 
   const [setting, setSetting] = useState({
     remind_time: 10,
   })
 
+  const [dataDir, setDataDir] = useState("") // 输入中的数据目录，点击移动后才保存
+  const [moving, setMoving] = useState(false)
+  const [webhookLog, setWebhookLog] = useState([]) // 最近的 webhook 投递记录
+  const [exportOptions, setExportOptions] = useState({ ...thisWeek(), status: "0", tags: "", format: "markdown", path: "" })
+  const [exporting, setExporting] = useState(false)
+  const [importOptions, setImportOptions] = useState({
+    path: "",
+    format: "csv",
+    mapping: { text: "text", date: "start", status: "status", percent: "percent", tags: "tags" },
+  })
+  const [importing, setImporting] = useState(false)
+  const [importResult, setImportResult] = useState(null)
+
   const debounceTimer = useRef(); // 防抖计时器
+  const remoteChange = useRef(false); // 配置来自其他窗口或外部修改，不需要再保存
 
 
   useEffect(() => {
 
     loadSetting()
-    getAutostart()
+    loadWebhookLog()
+
+    // 快捷键格式错误或被其他程序占用，包括窗口打开前启动时的冲突
+    invoke('take_shortcut_conflicts').then(showShortcutErrors)
+    const unlisten = listen("shortcut_conflict", (event) => {
+      showShortcutErrors(event.payload)
+    })
+    // 其他窗口保存或直接编辑 setting.json 后同步
+    const unlistenSetting = listen("setting_changed", (event) => {
+      setSetting(prev => {
+        if (JSON.stringify(prev) === JSON.stringify(event.payload)) {
+          return prev
+        }
+        remoteChange.current = true
+        setDataDir(event.payload.path)
+        return event.payload
+      })
+    })
+    return () => {
+      unlisten.then(fn => fn())
+      unlistenSetting.then(fn => fn())
+    }
   }, [])
 
 
+  function showShortcutErrors(errors) {
+    errors.forEach(item => {
+      toast.error(`快捷键 ${item.shortcut} 注册失败: ${item.error}`)
+    })
+  }
+
   async function loadSetting() {
 
     try {
       const setting = await invoke('load_setting');
       setSetting(setting)
+      setDataDir(setting.path)
     } catch (error) {
       toast.error(error)
     }
   }
 
-  async function getAutostart() {
-    const enabled = await invoke('plugin:autostart|is_enabled');
-    setIsOn(enabled)
+  async function loadWebhookLog() {
+    try {
+      setWebhookLog(await invoke('load_webhook_log'))
+    } catch (error) {
+      console.error(error)
+    }
+  }
+
+  function setWebhook(index, key, value) {
+    const webhooks = (setting.webhooks ?? []).map((hook, i) => i === index ? { ...hook, [key]: value } : hook)
+    setSetting({ ...setting, webhooks })
+  }
+
+  function toggleWebhookEvent(index, event) {
+    const events = setting.webhooks[index].events ?? []
+    setWebhook(index, "events", events.includes(event) ? events.filter(e => e !== event) : [...events, event])
+  }
+
+  function addWebhook() {
+    const hook = { enabled: true, url: "", events: ["task_created", "task_completed"], template: "", secret: "" }
+    setSetting({ ...setting, webhooks: [...(setting.webhooks ?? []), hook] })
+  }
+
+  function removeWebhook(index) {
+    setSetting({ ...setting, webhooks: setting.webhooks.filter((_, i) => i !== index) })
+  }
+
+  function setHook(index, key, value) {
+    const hooks = (setting.hooks ?? []).map((hook, i) => i === index ? { ...hook, [key]: value } : hook)
+    setSetting({ ...setting, hooks })
+  }
+
+  function addHook() {
+    const hook = { enabled: true, event: "task_completed", command: "", timeout_secs: 10 }
+    setSetting({ ...setting, hooks: [...(setting.hooks ?? []), hook] })
+  }
+
+  function removeHook(index) {
+    setSetting({ ...setting, hooks: setting.hooks.filter((_, i) => i !== index) })
+  }
+
+  // 该地址最近一次投递的结果
+  function lastDelivery(url) {
+    const record = webhookLog.findLast(r => r.url === url)
+    if (!record) {
+      return null
+    }
+    return record.success ? `最近一次发送成功 (${record.status})` : `最近一次发送失败: ${record.error ?? record.status}`
+  }
+
+  async function exportTasks() {
+    setExporting(true)
+    try {
+      const { start, end, status, tags, format, path } = exportOptions
+      const query = {
+        start: start || null,
+        end: end || null,
+        status,
+        tags: tags.split(/[,，\s]+/).map(tag => tag.replace(/^#/, "")).filter(Boolean),
+      }
+      const result = await invoke('export_tasks', { query, format, path })
+      toast.success(`已导出 ${result.count} 个任务到 ${result.path}`)
+    } catch (error) {
+      toast.error(`导出失败: ${error}`)
+    } finally {
+      setExporting(false)
+    }
   }
 
-  async function setAutostart(enabled) {
+  // dry_run 时只预览解析结果和重复的任务
+  async function importTasks(dryRun) {
+    setImporting(true)
     try {
-      if (enabled) {
-        await invoke('plugin:autostart|enable');
-      } else {
-        await invoke('plugin:autostart|disable');
+      const { path, format, mapping } = importOptions
+      const result = await invoke('import_tasks', { path, options: { format, mapping, dry_run: dryRun } })
+      setImportResult(result)
+      if (!dryRun) {
+        const failed = result.failed ? `，${result.failed} 个失败` : ""
+        toast.success(`已导入 ${result.created} 个任务，跳过 ${result.duplicates} 个重复${failed}`)
       }
-      setIsOn(enabled)
     } catch (error) {
-      console.error(error)
+      setImportResult(null)
+      toast.error(`导入失败: ${error}`)
+    } finally {
+      setImporting(false)
+    }
+  }
+
+  function setImportMapping(key, value) {
+    setImportOptions({ ...importOptions, mapping: { ...importOptions.mapping, [key]: value } })
+  }
+
+  function setStartup(key, value) {
+    setSetting({ ...setting, startup: { ...setting.startup, [key]: value } })
+  }
+
+  // 本地接口的 token，开启时没有 token 则自动生成
+  function generateToken() {
+    return crypto.randomUUID().replace(/-/g, "")
+  }
+
+  function setHttpApi(key, value) {
+    const httpApi = { ...setting.http_api, [key]: value }
+    if (httpApi.enabled && !httpApi.token) {
+      httpApi.token = generateToken()
+    }
+    setSetting({ ...setting, http_api: httpApi })
+  }
+
+  async function copyToken() {
+    try {
+      await navigator.clipboard.writeText(setting.http_api?.token ?? "")
+      toast.success("已复制 token")
+    } catch (error) {
+      toast.error(`复制失败: ${error}`)
     }
   }
 
   useEffect(() => {
+    if (remoteChange.current) {
+      remoteChange.current = false
+      return
+    }
     if (debounceTimer.current) {
       clearTimeout(debounceTimer.current)
     }
@@ -76,10 +280,34 @@ export default function Settings() {
     }, 1000)
   }, [setting])
 
+  // 修改数据目录，数据文件会移动到新目录，失败时保持原目录
+  async function moveDataDir() {
+    if (!dataDir.trim() || dataDir === setting.path) {
+      return
+    }
+    setMoving(true)
+    // 未保存的修改一并保存，避免防抖保存用旧目录覆盖
+    clearTimeout(debounceTimer.current)
+    try {
+      const newSetting = { ...setting, path: dataDir.trim() }
+      const res = await invoke('save_setting', { setting: newSetting })
+      showShortcutErrors(res.shortcut_errors)
+      remoteChange.current = true
+      setSetting(newSetting)
+      toast.success("数据目录已移动")
+    } catch (error) {
+      toast.error(`移动失败: ${error}`)
+      setDataDir(setting.path)
+    } finally {
+      setMoving(false)
+    }
+  }
+
   async function saveSetting() {
     try {
       console.log(setting)
-      await invoke('save_setting', { setting })
+      const res = await invoke('save_setting', { setting })
+      showShortcutErrors(res.shortcut_errors)
     } catch (error) {
       toast.error(error)
     }
@@ -110,7 +338,15 @@ export default function Settings() {
         </div>
         <div className="flex flex-row items-center justify-between gap-2">
           <Label htmlFor="autostart">开启自动启动</Label>
-          <Switch id="autostart" checked={isOn} onCheckedChange={setAutostart} />
+          <Switch id="autostart" checked={setting.startup?.launch_at_login ?? false} onCheckedChange={(v) => setStartup("launch_at_login", v)} />
+        </div>
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="start_hidden">启动时隐藏到托盘</Label>
+          <Switch id="start_hidden" checked={setting.startup?.start_hidden ?? false} onCheckedChange={(v) => setStartup("start_hidden", v)} />
+        </div>
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="plan_on_first_launch">每天首次启动时规划今日任务</Label>
+          <Switch id="plan_on_first_launch" checked={setting.startup?.plan_on_first_launch ?? true} onCheckedChange={(v) => setStartup("plan_on_first_launch", v)} />
         </div>
 
         <div className="flex flex-row items-center justify-between gap-2">
@@ -123,6 +359,201 @@ export default function Settings() {
           />
         </div>
 
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="anchor" className="text-ellipsis whitespace-nowrap">弹窗位置</Label>
+          <Select value={setting.anchor ?? "last"} onValueChange={(v) => setSetting({ ...setting, anchor: v })}>
+            <SelectTrigger id="anchor">
+              <SelectValue placeholder="弹窗位置" />
+            </SelectTrigger>
+            <SelectContent>
+              {anchorOptions.map(option => (
+                <SelectItem key={option.value} value={option.value}>{option.label}</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="shortcut_toggle" className="text-ellipsis whitespace-nowrap">显示/隐藏</Label>
+          <Input id="shortcut_toggle" value={setting.shortcuts?.toggle ?? ""} placeholder="CommandOrControl+Shift+T" onChange={
+            (e) => setSetting({ ...setting, shortcuts: { ...setting.shortcuts, toggle: e.target.value } })
+          } />
+        </div>
+
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="shortcut_quick_add" className="text-ellipsis whitespace-nowrap">快速添加</Label>
+          <Input id="shortcut_quick_add" value={setting.shortcuts?.quick_add ?? ""} placeholder="CommandOrControl+Shift+A" onChange={
+            (e) => setSetting({ ...setting, shortcuts: { ...setting.shortcuts, quick_add: e.target.value } })
+          } />
+        </div>
+
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="data_dir" className="text-ellipsis whitespace-nowrap">数据目录</Label>
+          <Input id="data_dir" value={dataDir} disabled={moving} onChange={(e) => setDataDir(e.target.value)} />
+          <Button variant="outline" size="sm" disabled={moving || dataDir === setting.path} onClick={moveDataDir}>移动</Button>
+        </div>
+
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label htmlFor="http_api">开启本地接口</Label>
+          <Switch id="http_api" checked={setting.http_api?.enabled ?? false} onCheckedChange={(v) => setHttpApi("enabled", v)} />
+        </div>
+        {setting.http_api?.enabled && (
+          <>
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Label htmlFor="http_api_port" className="text-ellipsis whitespace-nowrap">端口</Label>
+              <Input id="http_api_port" value={setting.http_api.port} type="text" inputMode="decimal" pattern="\d*" onChange={
+                (e) => {
+                  let v = e.target.value.replace(/\D/g, "").slice(0, 5);
+                  setHttpApi("port", Math.min(Number(v), 65535))
+                }}
+              />
+            </div>
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Label htmlFor="http_api_token" className="text-ellipsis whitespace-nowrap">Token</Label>
+              <Input id="http_api_token" value={setting.http_api.token} readOnly />
+              <Button variant="outline" size="sm" onClick={copyToken}>复制</Button>
+              <Button variant="outline" size="sm" onClick={() => setHttpApi("token", generateToken())}>重新生成</Button>
+            </div>
+          </>
+        )}
+
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label className="text-ellipsis whitespace-nowrap">Webhook</Label>
+          <Button variant="outline" size="sm" onClick={addWebhook}>添加</Button>
+        </div>
+        {(setting.webhooks ?? []).map((hook, index) => (
+          <div key={index} className="flex flex-col gap-2 border rounded-md p-2">
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Switch checked={hook.enabled} onCheckedChange={(v) => setWebhook(index, "enabled", v)} />
+              <Input value={hook.url} placeholder="https://example.com/hook" onChange={(e) => setWebhook(index, "url", e.target.value)} />
+              <Button variant="ghost" size="sm" onClick={() => removeWebhook(index)}>删除</Button>
+            </div>
+            <div className="flex flex-row items-center gap-2">
+              {webhookEvents.map(event => (
+                <Button key={event.value} size="sm" variant={hook.events?.includes(event.value) ? "default" : "outline"} onClick={() => toggleWebhookEvent(index, event.value)}>{event.label}</Button>
+              ))}
+            </div>
+            <Input value={hook.template} placeholder='模板（可选），例如 {"text": "已完成 {{task.text}}"}' onChange={(e) => setWebhook(index, "template", e.target.value)} />
+            <Input value={hook.secret} type="password" placeholder="签名密钥（可选）" onChange={(e) => setWebhook(index, "secret", e.target.value)} />
+            {lastDelivery(hook.url) && <div className="text-xs text-gray-500">{lastDelivery(hook.url)}</div>}
+          </div>
+        ))}
+
+        <div className="flex flex-row items-center justify-between gap-2">
+          <Label className="text-ellipsis whitespace-nowrap">事件命令</Label>
+          <Button variant="outline" size="sm" onClick={addHook}>添加</Button>
+        </div>
+        {(setting.hooks ?? []).map((hook, index) => (
+          <div key={index} className="flex flex-col gap-2 border rounded-md p-2">
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Switch checked={hook.enabled} onCheckedChange={(v) => setHook(index, "enabled", v)} />
+              <Select value={hook.event} onValueChange={(v) => setHook(index, "event", v)}>
+                <SelectTrigger>
+                  <SelectValue placeholder="事件" />
+                </SelectTrigger>
+                <SelectContent>
+                  {hookEvents.map(option => (
+                    <SelectItem key={option.value} value={option.value}>{option.label}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              <Button variant="ghost" size="sm" onClick={() => removeHook(index)}>删除</Button>
+            </div>
+            <Input value={hook.command} placeholder="命令，事件内容以 JSON 写入 stdin" onChange={(e) => setHook(index, "command", e.target.value)} />
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Label className="text-ellipsis whitespace-nowrap">超时(s)</Label>
+              <Input value={hook.timeout_secs} type="text" inputMode="decimal" pattern="\d*" onChange={
+                (e) => setHook(index, "timeout_secs", Number(e.target.value.replace(/\D/g, "")))
+              } />
+            </div>
+          </div>
+        ))}
+
+        {state === "success" && (
+          <div className="flex flex-col gap-2 border rounded-md p-2">
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Label className="text-ellipsis whitespace-nowrap">导出任务</Label>
+              <Input type="date" value={exportOptions.start} onChange={(e) => setExportOptions({ ...exportOptions, start: e.target.value })} />
+              <Input type="date" value={exportOptions.end} onChange={(e) => setExportOptions({ ...exportOptions, end: e.target.value })} />
+            </div>
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Select value={exportOptions.status} onValueChange={(v) => setExportOptions({ ...exportOptions, status: v })}>
+                <SelectTrigger>
+                  <SelectValue placeholder="状态" />
+                </SelectTrigger>
+                <SelectContent>
+                  {exportStatusOptions.map(option => (
+                    <SelectItem key={option.value} value={option.value}>{option.label}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              <Select value={exportOptions.format} onValueChange={(v) => setExportOptions({ ...exportOptions, format: v })}>
+                <SelectTrigger>
+                  <SelectValue placeholder="格式" />
+                </SelectTrigger>
+                <SelectContent>
+                  {exportFormats.map(option => (
+                    <SelectItem key={option.value} value={option.value}>{option.label}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+            <Input value={exportOptions.tags} placeholder="标签（可选），例如 工作, 周报" onChange={(e) => setExportOptions({ ...exportOptions, tags: e.target.value })} />
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Input value={exportOptions.path} placeholder="保存到的文件或目录" onChange={(e) => setExportOptions({ ...exportOptions, path: e.target.value })} />
+              <Button variant="outline" size="sm" disabled={exporting || !exportOptions.path.trim()} onClick={exportTasks}>导出</Button>
+            </div>
+          </div>
+        )}
+
+        {state === "success" && (
+          <div className="flex flex-col gap-2 border rounded-md p-2">
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Label className="text-ellipsis whitespace-nowrap">导入任务</Label>
+              <Select value={importOptions.format} onValueChange={(v) => { setImportOptions({ ...importOptions, format: v }); setImportResult(null) }}>
+                <SelectTrigger>
+                  <SelectValue placeholder="格式" />
+                </SelectTrigger>
+                <SelectContent>
+                  {importFormats.map(option => (
+                    <SelectItem key={option.value} value={option.value}>{option.label}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+            {importOptions.format === "csv" && (
+              <div className="grid grid-cols-5 gap-1">
+                {csvMappingFields.map(field => (
+                  <Input key={field.key} value={importOptions.mapping[field.key]} placeholder={field.label} title={`${field.label}列`} onChange={(e) => setImportMapping(field.key, e.target.value)} />
+                ))}
+              </div>
+            )}
+            <div className="flex flex-row items-center justify-between gap-2">
+              <Input value={importOptions.path} placeholder="要导入的文件" onChange={(e) => { setImportOptions({ ...importOptions, path: e.target.value }); setImportResult(null) }} />
+              <Button variant="outline" size="sm" disabled={importing || !importOptions.path.trim()} onClick={() => importTasks(true)}>预览</Button>
+              <Button variant="outline" size="sm" disabled={importing || !importResult?.dry_run} onClick={() => importTasks(false)}>导入</Button>
+            </div>
+            {importResult && (
+              <div className="flex flex-col gap-1 text-sm text-muted-foreground">
+                <span>
+                  {importResult.dry_run
+                    ? `共 ${importResult.items.length} 个任务，${importResult.duplicates} 个重复将跳过`
+                    : `已创建 ${importResult.created} 个，跳过 ${importResult.duplicates} 个重复，${importResult.failed} 个失败`}
+                  {importResult.errors.length > 0 && `，${importResult.errors.length} 行无法解析`}
+                </span>
+                {importResult.items.slice(0, 5).map(item => (
+                  <span key={item.line} className="truncate">
+                    {item.duplicate ? "重复 " : item.error ? "失败 " : ""}{item.task.time.start.slice(0, 10)} {item.task.text}
+                  </span>
+                ))}
+                {importResult.errors.slice(0, 3).map(error => (
+                  <span key={error} className="truncate text-destructive">{error}</span>
+                ))}
+              </div>
+            )}
+          </div>
+        )}
+
         {state === "success" && (
           <>
             <div className="flex flex-row items-center justify-between gap-2">
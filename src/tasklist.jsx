@@ -2,6 +2,7 @@
 
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { useNotionContext } from '@/context/NotionContext';
+import { getLocalISOStringWithTZ } from '@/lib/utils';
 import { AnimatePresence, motion } from 'framer-motion';
 
 import { Button } from '@/components/ui/button';
@@ -18,9 +19,10 @@ import Loading from '@/components/ui/loading';
 
 import { Plus, AlarmClock, CalendarDays, Bell } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { info } from '@tauri-apps/plugin-log';
 import { toast } from "sonner";
-import { startOfDay, startOfWeek, endOfDay, endOfWeek, startOfMonth, endOfMonth } from 'date-fns';
+import { startOfDay, startOfWeek, endOfDay, endOfWeek, startOfMonth, endOfMonth, isToday, isThisWeek } from 'date-fns';
 import { v4 as uuidv4 } from 'uuid';
 import { open as openShell } from "@tauri-apps/plugin-shell";
 
@@ -53,6 +55,7 @@ export default function TaskList() {
     // 用于控制自动获取焦点
     const taskRefs = useRef({});
     const [focusTaskId, setFocusTaskId] = useState(null);
+    const [openTask, setOpenTask] = useState(null);   // 通过链接打开的任务
     const processedFocusId = useRef(null); // 新增一个ref来记录已处理的ID
 
     const [items, setItems] = useState([]);
@@ -103,7 +106,27 @@ export default function TaskList() {
 
         // 决定是新增还是更新
         try {
-            if (itemToSave.id === "") { // ID为空，说明是新任务
+            if (itemToSave.id === "" && itemToSave.text.trim().startsWith("/")) { // 斜杠命令，例如 /done 2
+                setItems(prev => prev.filter(item => item.localId !== localId));
+                const res = await invoke("run_slash_command", { input: itemToSave.text });
+                if (!res.success) {
+                    toast.error(res.message);
+                } else if (res.tasks) {
+                    toast.info(res.message, { description: res.tasks.map((task, i) => `${i + 1}. ${task.text}`).join("\n") });
+                } else {
+                    toast.success(res.message);
+                }
+            } else if (itemToSave.id === "") { // ID为空，说明是新任务
+                // 识别文本中的日期、时间、标签和权重，例如 "明天下午3点 写周报 #工作 30%"
+                const { task: parsed, has_date } = await invoke("parse_task", { text: itemToSave.text });
+                if (parsed.text) {
+                    itemToSave.text = parsed.text;
+                    itemToSave.tags = [...new Set([...(itemToSave.tags || []), ...parsed.tags])];
+                    itemToSave.percent = parsed.percent || itemToSave.percent;
+                    if (has_date) {
+                        itemToSave.time = parsed.time;
+                    }
+                }
                 if (itemToSave.status === "完成") {
                     itemToSave.time.end = getLocalISOStringWithTZ();
                 }
@@ -247,6 +270,81 @@ export default function TaskList() {
         await loadTasks(tab.id);
     }
 
+    // 休眠唤醒或长时间离开后回来，切换到今日任务
+    useEffect(() => {
+        const unlisten = listen("welcome_back", () => {
+            toast.info("欢迎回来，先看看今天最重要的事")
+            changeTab(tabOptions[0])
+        })
+        return () => {
+            unlisten.then(f => f())
+        }
+    }, [loadTasks])
+
+    // 每天首次启动时显示今日任务
+    useEffect(() => {
+        invoke("take_plan_day").then(plan => {
+            if (plan) {
+                setSelectedTab(tabOptions[0])
+                toast.info("新的一天，先规划今天的任务")
+            }
+        })
+    }, [])
+
+    // 通过 task-reminder://open/<id> 链接打开任务，启动时的链接由前端主动获取
+    useEffect(() => {
+        const takeOpenTask = () => {
+            invoke("take_open_task").then(task => {
+                if (task) {
+                    setOpenTask(task)
+                }
+            })
+        }
+        takeOpenTask()
+        const unlisten = listen("open_task", takeOpenTask)
+        return () => {
+            unlisten.then(f => f())
+        }
+    }, [])
+
+    // 登录信息加载后切换到任务所在的列表并聚焦
+    useEffect(() => {
+        if (!openTask || state !== "success") {
+            return
+        }
+        const date = new Date(openTask.time.start)
+        const tab = isToday(date) ? tabOptions[0] : isThisWeek(date) ? tabOptions[1] : tabOptions[2]
+        setSelectedTab(tab)
+        loadTasks(tab.id, date).then(() => setFocusTaskId(openTask.id))
+        toast.info(openTask.text, { description: openTask.time.start })
+        setOpenTask(null)
+    }, [openTask, state, loadTasks])
+
+    // 配置文件损坏并已恢复时提示
+    useEffect(() => {
+        invoke("take_setting_warning").then(message => {
+            if (message) {
+                toast.warning(message)
+            }
+        })
+        const unlisten = listen("setting_warning", (event) => {
+            toast.warning(event.payload)
+        })
+        return () => {
+            unlisten.then(f => f())
+        }
+    }, [])
+
+    // 托盘菜单中完成任务或同步后，重新加载当前列表
+    useEffect(() => {
+        const unlisten = listen("tasks_changed", () => {
+            loadTasks(selectedTab.id)
+        })
+        return () => {
+            unlisten.then(f => f())
+        }
+    }, [loadTasks, selectedTab])
+
     return (
         <MenuView loadTasks={() => loadTasks(selectedTab.id)}>
             <div className="flex flex-col h-screen">
@@ -411,20 +509,3 @@ const Footer = ({ handleAddTask, remindLaterHours, setRemindLaterHours, remindLa
         </div>
     )
 }
-
-
-
-function getLocalISOStringWithTZ() {
-    const date = new Date();
-    const tzo = -date.getTimezoneOffset();
-    const dif = tzo >= 0 ? '+' : '-';
-    const pad = n => `${Math.floor(Math.abs(n))}`.padStart(2, '0');
-    return date.getFullYear() +
-        '-' + pad(date.getMonth() + 1) +
-        '-' + pad(date.getDate()) +
-        'T' + pad(date.getHours()) +
-        ':' + pad(date.getMinutes()) +
-        ':' + pad(date.getSeconds()) +
-        dif + pad(tzo / 60) +
-        ':' + pad(tzo % 60);
-}
\ No newline at end of file
@@ -0,0 +1,94 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { startOfDay, endOfDay } from "date-fns";
+import { Check, Maximize2, RefreshCw } from "lucide-react";
+
+import { Button } from "@/components/ui/button";
+import { useNotionContext } from "@/context/NotionContext";
+
+// 小组件只展示的任务数
+const TOP_COUNT = 3
+
+export default function Widget() {
+    const { state, authInfo } = useNotionContext();
+    const [items, setItems] = useState([]);
+
+    const loadTasks = useCallback(async () => {
+        if (state !== "success" || !authInfo?.duplicated_template_id) {
+            return
+        }
+        try {
+            const now = new Date()
+            const res = await invoke("load_tasks", {
+                params: {
+                    start: startOfDay(now).toISOString(),
+                    end: endOfDay(now).toISOString(),
+                    status: "1"
+                }
+            })
+            if (res.success) {
+                setItems(res.tasks.tasks.slice(0, TOP_COUNT))
+            }
+        } catch (err) {
+            toast.error(err.toString())
+        }
+    }, [state, authInfo])
+
+    useEffect(() => {
+        loadTasks()
+        // 定时刷新，保持与主窗口一致
+        const timer = setInterval(loadTasks, 60 * 1000)
+        return () => clearInterval(timer)
+    }, [loadTasks])
+
+    // 一键完成
+    async function completeTask(task) {
+        const itemToSave = { ...task, status: "完成", time: { ...task.time, end: new Date().toISOString() } }
+        setItems(prev => prev.filter(item => item.id !== task.id))
+        try {
+            const res = await invoke("update_task", { task: itemToSave })
+            if (!res.success) {
+                toast.error("更新失败")
+            }
+        } catch (err) {
+            toast.error(err.toString())
+        } finally {
+            loadTasks()
+        }
+    }
+
+    async function fullMode() {
+        try {
+            await invoke("set_window_mode", { mode: "full" })
+        } catch (err) {
+            toast.error(err.toString())
+        }
+    }
+
+    return (
+        <div className="flex flex-col h-screen select-none">
+            <div data-tauri-drag-region className="flex flex-row justify-between items-center h-8 px-2 text-xs font-bold">
+                <span data-tauri-drag-region>今日最重要的事</span>
+                <div className="flex flex-row items-center">
+                    <Button variant="ghost" size="icon" onClick={loadTasks}><RefreshCw size={12} /></Button>
+                    <Button variant="ghost" size="icon" onClick={fullMode}><Maximize2 size={12} /></Button>
+                </div>
+            </div>
+            <div className="flex-1 flex flex-col gap-1 px-2 pb-2 overflow-y-auto">
+                {items.length === 0 &&
+                    <div className="text-xs text-gray-500 text-center pt-4">今天的任务都完成了</div>
+                }
+                {items.map(item => (
+                    <div key={item.id} className="flex flex-row items-center justify-between gap-2 rounded-md bg-muted px-2 py-1 text-sm">
+                        <span className="truncate">{item.text}</span>
+                        <div className="flex flex-row items-center gap-1">
+                            <span className="text-xs text-gray-500">{item.percent}%</span>
+                            <Button variant="ghost" size="icon" onClick={() => completeTask(item)}><Check size={14} /></Button>
+                        </div>
+                    </div>
+                ))}
+            </div>
+        </div>
+    )
+}